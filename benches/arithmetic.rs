@@ -4,8 +4,8 @@ extern crate criterion;
 use criterion::Criterion;
 
 use jpar::branch::alternative;
-use jpar::characters::{decimal_digit1, read_any_of, read_char, ucd_inline_whitespace0};
-use jpar::helpers::map_result;
+use jpar::characters::{read_any_of, read_char, ucd_inline_whitespace0};
+use jpar::numbers::unsigned;
 use jpar::sequence::{delimited, repeat_and_fold, tuple};
 use jpar::verifiers::text_verifier;
 use jpar::ParserInput;
@@ -21,14 +21,7 @@ use jpar::ParserResult;
 fn factor(input: &mut ParserInput) -> ParserResult<i64> {
     delimited(
         ucd_inline_whitespace0,
-        alternative((
-            map_result(decimal_digit1, |_, digits| {
-                unsafe { std::str::from_utf8_unchecked(digits.as_bytes()) }
-                    .parse()
-                    .unwrap()
-            }),
-            delimited(read_char('('), expr, read_char(')')),
-        )),
+        alternative((unsigned(), delimited(read_char('('), expr, read_char(')')))),
         ucd_inline_whitespace0,
     )(input)
 }
@@ -68,6 +61,7 @@ fn expr(input: &mut ParserInput) -> ParserResult<i64> {
     )(input)
 }
 
+#[allow(clippy::eq_op, clippy::erasing_op, clippy::identity_op)]
 fn arithmetic_bench(c: &mut Criterion) {
     let data = "  2*2 / ( 5 - 1) + 3 / 4 * (2 - 7 + 567 *12 /2) + 3*(1+2*( 45 /2));";
     let mut reader = ParserInput::new(data);
@@ -88,6 +82,16 @@ fn arithmetic_bench(c: &mut Criterion) {
             expr(&mut reader).unwrap()
         });
     });
+
+    c.bench_function("arithmetic lazy", |b| {
+        let mut reader = ParserInput::new(data);
+        reader.set_lazy_tracking(true);
+        let init_cursor = reader.save_cursor();
+        b.iter(|| {
+            reader.restore(init_cursor.clone());
+            expr(&mut reader).unwrap()
+        });
+    });
 }
 
 criterion_group!(benches, arithmetic_bench);
@@ -12,7 +12,10 @@ use jpar::characters::{
 use jpar::combinator::verify;
 use jpar::helpers::{and_then, map_result};
 use jpar::numbers::{read_float, read_integer};
-use jpar::sequence::{delimited, preceded, repeat_and_fold, repeat_separated, separated_tuple};
+use jpar::sequence::{
+    delimited, preceded, repeat_collect, repeat_separated, repeat_separated_collect,
+    separated_tuple,
+};
 use jpar::verifiers::text_verifier;
 use jpar::ParserInput;
 use jpar::{ParserResult, ParserResultError};
@@ -94,15 +97,7 @@ fn character<Err>(input: &mut ParserInput<Err>) -> ParserResult<char, Err> {
 fn string<Err>(input: &mut ParserInput<Err>) -> ParserResult<String, Err> {
     delimited(
         read_char('"'),
-        repeat_and_fold(
-            ..,
-            String::new(),
-            |mut string, c| {
-                string.push(c);
-                string
-            },
-            character,
-        ),
+        repeat_collect(.., character),
         read_char('"'),
     )(input)
 }
@@ -129,17 +124,14 @@ fn array<Err: From<&'static str>>(
 fn object<Err: From<&'static str>>(
     input: &mut ParserInput<Err>,
 ) -> ParserResult<HashMap<String, JsonValue>, Err> {
-    map_result(
-        delimited(
-            read_char('{'),
-            ws(repeat_separated(
-                ..,
-                separated_tuple((string, json_value), ws(read_char(':'))),
-                ws(read_char(',')),
-            )),
-            read_char('}'),
-        ),
-        |_, key_values| key_values.into_iter().collect(),
+    delimited(
+        read_char('{'),
+        ws(repeat_separated_collect(
+            ..,
+            separated_tuple((string, json_value), ws(read_char(':'))),
+            ws(read_char(',')),
+        )),
+        read_char('}'),
     )(input)
 }
 
@@ -178,6 +170,14 @@ fn json_bench(c: &mut Criterion) {
             json(&mut reader).unwrap()
         });
     });
+
+    c.bench_function("json lazy", |b| {
+        b.iter(|| {
+            let mut reader = ParserInput::new_with_error::<&str>(data);
+            reader.set_lazy_tracking(true);
+            json(&mut reader).unwrap()
+        });
+    });
 }
 
 fn read_integer_bench(c: &mut Criterion) {
@@ -0,0 +1,625 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::{Cursor, LineIndex, PositionPolicy, Span};
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_PRIMARY: &str = "\x1b[1;31m";
+const ANSI_SECONDARY: &str = "\x1b[1;34m";
+
+/// A human readable report that points to one or more locations of the input,
+/// similar to the ones emitted by rustc.
+///
+/// The lines are delimited following the [PositionPolicy] of the primary span.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{Diagnostic, ParserInput};
+/// let mut reader = ParserInput::new("let x = tru;");
+/// reader.read_text("let x = ");
+/// let from = reader.save_cursor();
+/// reader.read_text("tru");
+/// let span = reader.substring_to_current(&from);
+///
+/// let diagnostic = Diagnostic::new("unknown value", span).with_label("expected a boolean");
+/// assert_eq!(
+///     diagnostic.to_string(),
+///     "error: unknown value\n --> 1:9\n  |\n1 | let x = tru;\n  |         ^^^ expected a boolean\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Diagnostic<'a> {
+    message: String,
+    span: Span<'a>,
+    path: Option<String>,
+    label: Option<String>,
+    secondary: Vec<(Span<'a>, String)>,
+    colored: bool,
+    tab_width: Option<usize>,
+}
+
+impl<'a> Diagnostic<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `Diagnostic` pointing to `span`.
+    pub fn new(message: impl Into<String>, span: Span<'a>) -> Diagnostic<'a> {
+        Diagnostic {
+            message: message.into(),
+            span,
+            path: None,
+            label: None,
+            secondary: Vec::new(),
+            colored: false,
+            tab_width: None,
+        }
+    }
+
+    /// Builds a new `Diagnostic` pointing to the position of `cursor` inside `content`
+    /// whose lines follow `policy`, usually the one of the reader.
+    ///
+    /// **Note**: `cursor` must belong to `content`. Lazy cursors are resolved against it.
+    pub fn new_at_cursor(
+        message: impl Into<String>,
+        content: &'a str,
+        cursor: &Cursor,
+        policy: &PositionPolicy,
+    ) -> Diagnostic<'a> {
+        let cursor = cursor.resolve(content, policy);
+        Diagnostic::new(message, Span::new(content, cursor.clone(), cursor, *policy))
+    }
+
+    /// Builds a new `Diagnostic` from the data of a `ParserResultError::Error`
+    /// using the error as message. See [Diagnostic::new_at_cursor()].
+    ///
+    /// **Note**: the cursor of `error` must belong to `content`.
+    pub fn from_error<Err: fmt::Display>(
+        content: &'a str,
+        error: &(Cursor, Err),
+        policy: &PositionPolicy,
+    ) -> Diagnostic<'a> {
+        Diagnostic::new_at_cursor(error.1.to_string(), content, &error.0, policy)
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The main message of the `Diagnostic`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The primary location of the `Diagnostic`.
+    pub fn span(&self) -> &Span<'a> {
+        &self.span
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets the path of the file shown before the primary location.
+    pub fn with_path(mut self, path: impl Into<String>) -> Diagnostic<'a> {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the label shown next to the primary location.
+    pub fn with_label(mut self, label: impl Into<String>) -> Diagnostic<'a> {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Adds a secondary location with its label.
+    ///
+    /// **Note**: `span` must belong to the same content as the primary one.
+    pub fn with_secondary(mut self, span: Span<'a>, label: impl Into<String>) -> Diagnostic<'a> {
+        self.secondary.push((span, label.into()));
+        self
+    }
+
+    /// Sets whether the output includes ANSI colour codes or not. By default it is plain text.
+    pub fn with_colors(mut self, colored: bool) -> Diagnostic<'a> {
+        self.colored = colored;
+        self
+    }
+
+    /// Sets the number of columns a tab advances to. By default it is the one of the
+    /// position policy of the primary span, so that the caret matches the column of the header.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Diagnostic<'a> {
+        self.tab_width = Some(tab_width);
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Renders the `Diagnostic` as a `String`.
+    pub fn render(&self) -> String {
+        self.to_string()
+    }
+
+    /// Wraps `text` with the ANSI `color` if the colours are enabled.
+    fn paint<'b>(&self, color: &'static str, text: &'b str) -> Painted<'b> {
+        Painted {
+            color: if self.colored { color } else { "" },
+            text,
+        }
+    }
+
+    /// The number of columns a tab advances to.
+    fn tab_width(&self) -> usize {
+        self.tab_width
+            .unwrap_or_else(|| self.span.position_policy().tab_width())
+            .max(1)
+    }
+
+    /// Writes a source line (without its line break) expanding the tabs.
+    fn write_source_line(&self, f: &mut fmt::Formatter<'_>, line: &str) -> fmt::Result {
+        let tab_width = self.tab_width();
+        let mut column = 0;
+        for c in line.chars() {
+            if c == '\t' {
+                let width = tab_width - column % tab_width;
+                for _ in 0..width {
+                    f.write_char(' ')?;
+                }
+
+                column += width;
+            } else {
+                f.write_char(c)?;
+                column += c.width().unwrap_or(0);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The number of columns `text` occupies when displayed from the beginning of a line.
+    fn display_width(&self, text: &str) -> usize {
+        self.display_width_from(0, text)
+    }
+
+    /// The number of columns `text` occupies when displayed from `column`.
+    fn display_width_from(&self, column: usize, text: &str) -> usize {
+        let tab_width = self.tab_width();
+        let mut current = column;
+        for c in text.chars() {
+            if c == '\t' {
+                current += tab_width - current % tab_width;
+            } else {
+                current += c.width().unwrap_or(0);
+            }
+        }
+
+        current - column
+    }
+}
+
+impl<'a> fmt::Display for Diagnostic<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let content = self.span.whole_content();
+        let index = LineIndex::new_with_policy(content, *self.span.position_policy());
+
+        // Collect the annotations.
+        let mut annotations = Vec::with_capacity(self.secondary.len() + 1);
+        annotations.push(Annotation::new(
+            &index,
+            &self.span,
+            self.label.as_deref(),
+            true,
+        ));
+
+        for (span, label) in &self.secondary {
+            annotations.push(Annotation::new(&index, span, Some(label.as_str()), false));
+        }
+
+        // Collect the lines to show.
+        let mut lines: Vec<usize> = Vec::new();
+        for annotation in &annotations {
+            for line in annotation.start_line..=annotation.end_line {
+                lines.push(line);
+            }
+        }
+
+        lines.sort_unstable();
+        lines.dedup();
+
+        let gutter_width = lines.last().map_or(1, |v| v.to_string().len());
+        let gutter = " ".repeat(gutter_width);
+
+        // Header.
+        writeln!(
+            f,
+            "{}{}",
+            self.paint(ANSI_PRIMARY, "error"),
+            self.paint(ANSI_BOLD, &format!(": {}", self.message))
+        )?;
+        write!(f, "{}{} ", gutter, self.paint(ANSI_SECONDARY, "-->"))?;
+        if let Some(path) = &self.path {
+            write!(f, "{}:", path)?;
+        }
+        writeln!(
+            f,
+            "{}:{}",
+            self.span.start_cursor().line(),
+            self.span.start_cursor().column()
+        )?;
+        writeln!(f, "{} {}", gutter, self.paint(ANSI_SECONDARY, "|"))?;
+
+        // Lines.
+        let mut previous_line = None;
+
+        for line in lines {
+            if let Some(previous_line) = previous_line {
+                if line > previous_line + 1 {
+                    writeln!(f, "{}", self.paint(ANSI_SECONDARY, "..."))?;
+                }
+            }
+
+            let line_start = index.line_start(line).unwrap().byte_offset();
+            let mut text = index.line_content(line).unwrap();
+            if text.ends_with('\r') {
+                text = &text[..text.len() - 1];
+            }
+
+            let number = line.to_string();
+            write!(
+                f,
+                "{}{} {} ",
+                " ".repeat(gutter_width - number.len()),
+                self.paint(ANSI_SECONDARY, &number),
+                self.paint(ANSI_SECONDARY, "|")
+            )?;
+            self.write_source_line(f, text)?;
+            writeln!(f)?;
+
+            // Markers.
+            for annotation in &annotations {
+                if line < annotation.start_line || line > annotation.end_line {
+                    continue;
+                }
+
+                let from = annotation
+                    .start
+                    .max(line_start)
+                    .min(line_start + text.len());
+                let to = annotation.end.min(line_start + text.len()).max(from);
+                let padding = self.display_width(&content[line_start..from]);
+                let width = self.display_width_from(padding, &content[from..to]).max(1);
+                let color = if annotation.primary {
+                    ANSI_PRIMARY
+                } else {
+                    ANSI_SECONDARY
+                };
+                let marker = if annotation.primary { "^" } else { "-" };
+
+                write!(
+                    f,
+                    "{} {} {}{}",
+                    gutter,
+                    self.paint(ANSI_SECONDARY, "|"),
+                    " ".repeat(padding),
+                    self.paint(color, &marker.repeat(width))
+                )?;
+
+                match annotation.label {
+                    Some(label) if line == annotation.end_line => {
+                        writeln!(f, " {}", self.paint(color, label))?
+                    }
+                    _ => writeln!(f)?,
+                }
+            }
+
+            previous_line = Some(line);
+        }
+
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// A location to mark in a `Diagnostic`.
+struct Annotation<'b> {
+    start: usize,
+    end: usize,
+    start_line: usize,
+    end_line: usize,
+    label: Option<&'b str>,
+    primary: bool,
+}
+
+impl<'b> Annotation<'b> {
+    fn new(
+        index: &LineIndex,
+        span: &Span,
+        label: Option<&'b str>,
+        primary: bool,
+    ) -> Annotation<'b> {
+        let start = span.start_cursor().byte_offset();
+        let end = span.end_cursor().byte_offset();
+        let start_line = index.line_of(start);
+
+        // A span that ends just after a line break is shown in the previous line.
+        let mut end_line = index.line_of(end);
+        if end_line > start_line && index.line_start(end_line).unwrap().byte_offset() == end {
+            end_line -= 1;
+        }
+
+        Annotation {
+            start,
+            end,
+            start_line,
+            end_line,
+            label,
+            primary,
+        }
+    }
+}
+
+/// A text that is written with an ANSI colour.
+struct Painted<'b> {
+    color: &'static str,
+    text: &'b str,
+}
+
+impl<'b> fmt::Display for Painted<'b> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.color.is_empty() {
+            f.write_str(self.text)
+        } else {
+            write!(f, "{}{}{}", self.color, self.text, ANSI_RESET)
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::ParserInput;
+
+    use super::*;
+
+    fn span_of<'a>(content: &'a str, text: &str) -> Span<'a> {
+        let start = content.find(text).unwrap();
+        let mut reader = ParserInput::new(content);
+        reader.read_quantified(content[..start].chars().count());
+        let from = reader.save_cursor();
+        reader.read_quantified(text.chars().count());
+
+        reader.substring_to_current(&from)
+    }
+
+    #[test]
+    fn test_render_single_line() {
+        let content = "let a = 1;\nlet b = a + c;\n";
+        let diagnostic = Diagnostic::new("unknown variable", span_of(content, "c"))
+            .with_label("not found")
+            .with_secondary(span_of(content, "let b"), "in this statement");
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: unknown variable
+ --> 2:13
+  |
+2 | let b = a + c;
+  |             ^ not found
+  | ----- in this statement
+"
+        );
+    }
+
+    #[test]
+    fn test_render_multiline() {
+        let content = "fn main() {\n    call(\n        1,\n    );\n}";
+        let diagnostic = Diagnostic::new("bad call", span_of(content, "call(\n        1,\n    )"))
+            .with_secondary(span_of(content, "fn"), "function");
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: bad call
+ --> 2:5
+  |
+1 | fn main() {
+  | -- function
+2 |     call(
+  |     ^^^^^
+3 |         1,
+  | ^^^^^^^^^^
+4 |     );
+  | ^^^^^
+"
+        );
+    }
+
+    #[test]
+    fn test_render_gap_and_gutter() {
+        let content = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk";
+        let diagnostic = Diagnostic::new("twice", span_of(content, "k"))
+            .with_secondary(span_of(content, "b"), "first");
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: twice
+  --> 11:1
+   |
+ 2 | b
+   | - first
+...
+11 | k
+   | ^
+"
+        );
+    }
+
+    #[test]
+    fn test_render_tabs_and_wide_chars() {
+        let content = "\tx = \"日本\" + y";
+        let diagnostic = Diagnostic::new("wide", span_of(content, "y")).with_label("here");
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: wide
+ --> 1:13
+  |
+1 |  x = \"日本\" + y
+  |               ^ here
+"
+        );
+
+        let diagnostic = Diagnostic::new("wide", span_of(content, "日本"))
+            .with_tab_width(2)
+            .with_label("here");
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: wide
+ --> 1:7
+  |
+1 |   x = \"日本\" + y
+  |        ^^^^ here
+"
+        );
+    }
+
+    #[test]
+    fn test_render_tabs_policy() {
+        let mut reader = ParserInput::new("a\tb\n\t\tc");
+        reader.set_position_policy(PositionPolicy::new().with_tab_width(4));
+        reader.read_text("a\t");
+        let from = reader.save_cursor();
+        reader.read_text("b");
+        let diagnostic = Diagnostic::new("tab", reader.substring_to_current(&from));
+
+        // The caret is drawn at the column of the header.
+        assert_eq!(
+            diagnostic.render(),
+            "error: tab
+ --> 1:5
+  |
+1 | a   b
+  |     ^
+"
+        );
+
+        reader.read_text("\n\t\t");
+        let from = reader.save_cursor();
+        reader.read_text("c");
+        let diagnostic = Diagnostic::new("tab", reader.substring_to_current(&from));
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: tab
+ --> 2:9
+  |
+2 |         c
+  |         ^
+"
+        );
+    }
+
+    #[test]
+    fn test_render_cursor() {
+        let mut reader = ParserInput::new_with_error::<&str>("abc\r\ndef");
+        reader.read_text("abc\r\nde");
+        let error = (reader.save_cursor(), "unexpected character");
+        let diagnostic = Diagnostic::from_error(reader.content(), &error, reader.position_policy());
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: unexpected character
+ --> 2:3
+  |
+2 | def
+  |   ^
+"
+        );
+
+        // At the end of a line.
+        let mut reader = ParserInput::new("abc\ndef");
+        reader.read_text("abc");
+        let diagnostic = Diagnostic::new_at_cursor(
+            "missing",
+            reader.content(),
+            &reader.save_cursor(),
+            reader.position_policy(),
+        );
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: missing
+ --> 1:4
+  |
+1 | abc
+  |    ^
+"
+        );
+    }
+
+    #[test]
+    fn test_render_position_policy() {
+        let mut reader = ParserInput::new_with_error::<&str>("a\rb\rc");
+        reader.set_position_policy(PositionPolicy::new().with_carriage_return(true));
+        let start = reader.save_cursor();
+        reader.read_text("a\rb");
+        let first = reader.substring_to_current(&start);
+        reader.read_text("\r");
+        let error = (reader.save_cursor(), "unexpected character");
+        let diagnostic = Diagnostic::from_error(reader.content(), &error, reader.position_policy());
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: unexpected character
+ --> 3:1
+  |
+3 | c
+  | ^
+"
+        );
+
+        let from = reader.save_cursor();
+        reader.read_text("c");
+        let span = reader.substring_to_current(&from);
+        let diagnostic = Diagnostic::new("two lines", first).with_secondary(span, "here");
+
+        assert_eq!(
+            diagnostic.render(),
+            "error: two lines
+ --> 1:1
+  |
+1 | a
+  | ^
+2 | b
+  | ^
+3 | c
+  | - here
+"
+        );
+    }
+
+    #[test]
+    fn test_render_colored() {
+        let content = "abc";
+        let diagnostic = Diagnostic::new("oops", span_of(content, "b"))
+            .with_label("here")
+            .with_colors(true);
+
+        assert_eq!(
+            diagnostic.render(),
+            "\x1b[1;31merror\x1b[0m\x1b[1m: oops\x1b[0m
+ \x1b[1;34m-->\x1b[0m 1:2
+  \x1b[1;34m|\x1b[0m
+\x1b[1;34m1\x1b[0m \x1b[1;34m|\x1b[0m abc
+  \x1b[1;34m|\x1b[0m  \x1b[1;31m^\x1b[0m \x1b[1;31mhere\x1b[0m
+"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use crate::Cursor;
+
+/// Something that a parser expected to find in the input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expected<'a> {
+    /// A specific character.
+    Char(char),
+    /// A specific text.
+    Text(&'a str),
+    /// A specific sequence of bytes.
+    Bytes(&'a [u8]),
+    /// Any character inside the intervals.
+    Interval(&'static [RangeInclusive<char>]),
+    /// A named element of the grammar.
+    Label(&'a str),
+    /// The end of the input.
+    End,
+}
+
+impl<'a> fmt::Display for Expected<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Char(c) => write!(f, "`{}`", c.escape_debug()),
+            Expected::Text(text) => write!(f, "`{}`", text.escape_debug()),
+            Expected::Bytes(bytes) => {
+                write!(f, "b\"")?;
+
+                for byte in bytes.iter() {
+                    write!(f, "{}", core::ascii::escape_default(*byte))?;
+                }
+
+                write!(f, "\"")
+            }
+            Expected::Interval(intervals) => {
+                write!(f, "[")?;
+
+                for range in intervals.iter() {
+                    if range.start() == range.end() {
+                        write!(f, "{}", range.start().escape_debug())?;
+                    } else {
+                        write!(
+                            f,
+                            "{}-{}",
+                            range.start().escape_debug(),
+                            range.end().escape_debug()
+                        )?;
+                    }
+                }
+
+                write!(f, "]")
+            }
+            Expected::Label(label) => write!(f, "{}", label),
+            Expected::End => write!(f, "end of input"),
+        }
+    }
+}
+
+/// An error type that can record the context in which it happened.
+/// See [label()](crate::helpers::label).
+pub trait ContextError<'a> {
+    /// Adds `label` to the context chain of the error. `cursor` is the position in which the
+    /// labelled parser started. The contexts are added from the innermost to the outermost.
+    fn add_context(&mut self, label: &'a str, cursor: Cursor);
+}
+
+impl<'a> ContextError<'a> for () {
+    fn add_context(&mut self, _label: &'a str, _cursor: Cursor) {}
+}
+
+/// The built-in error type. It records the furthest position in which the
+/// parsers have failed and everything they expected to find there.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParserError<'a> {
+    cursor: Cursor,
+    expected: Vec<Expected<'a>>,
+    context: Vec<(&'a str, Cursor)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> ParserError<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `ParserError` without any expectation.
+    pub fn new(cursor: Cursor) -> ParserError<'a> {
+        ParserError {
+            cursor,
+            expected: Vec::new(),
+            context: Vec::new(),
+        }
+    }
+
+    /// Builds a new `ParserError` with a single expectation.
+    pub fn new_expected(cursor: Cursor, expected: Expected<'a>) -> ParserError<'a> {
+        ParserError {
+            cursor,
+            expected: vec![expected],
+            context: Vec::new(),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The position in which the error happened.
+    pub fn cursor(&self) -> &Cursor {
+        &self.cursor
+    }
+
+    /// The list of things expected at the position of the error.
+    pub fn expected(&self) -> &[Expected<'a>] {
+        &self.expected
+    }
+
+    /// The labels of the rules that were being parsed when the error happened,
+    /// along with their start positions. They are ordered from the innermost to the outermost.
+    pub fn context(&self) -> &[(&'a str, Cursor)] {
+        &self.context
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Adds a new expectation to the error if it is not already present.
+    pub fn add_expected(&mut self, expected: Expected<'a>) {
+        if !self.expected.contains(&expected) {
+            self.expected.push(expected);
+        }
+    }
+
+    /// Removes the expectations after the first `length` ones.
+    pub(crate) fn truncate_expected(&mut self, length: usize) {
+        self.expected.truncate(length);
+    }
+
+    /// Merges two errors keeping the one at the furthest position.
+    /// If both are at the same position, their expectations are joined.
+    pub fn merge(mut self, other: ParserError<'a>) -> ParserError<'a> {
+        let offset = self.cursor.byte_offset();
+        let other_offset = other.cursor.byte_offset();
+
+        if offset < other_offset {
+            return other;
+        }
+
+        if offset == other_offset {
+            for expected in other.expected {
+                self.add_expected(expected);
+            }
+        }
+
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> fmt::Display for ParserError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.expected.as_slice() {
+            [] => write!(f, "unexpected input")?,
+            [expected] => write!(f, "expected {}", expected)?,
+            [first, rest @ ..] => {
+                write!(f, "expected one of {}", first)?;
+
+                for expected in rest {
+                    write!(f, ", {}", expected)?;
+                }
+            }
+        }
+
+        write!(f, " at {}:{}", self.cursor.line(), self.cursor.column())?;
+
+        for (i, (label, _)) in self.context.iter().rev().enumerate() {
+            if i == 0 {
+                write!(f, ", while parsing {}", label)?;
+            } else {
+                write!(f, " → while parsing {}", label)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> ContextError<'a> for ParserError<'a> {
+    fn add_context(&mut self, label: &'a str, cursor: Cursor) {
+        self.context.push((label, cursor));
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> std::error::Error for ParserError<'a> {}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::parsers::characters::DECIMAL_DIGITS_CHARS;
+
+    use super::*;
+
+    #[test]
+    fn test_expected_display() {
+        assert_eq!(Expected::Char('\n').to_string(), "`\\n`");
+        assert_eq!(Expected::Text("true").to_string(), "`true`");
+        assert_eq!(
+            Expected::Interval(DECIMAL_DIGITS_CHARS).to_string(),
+            "[0-9]"
+        );
+        assert_eq!(
+            Expected::Interval(&['a'..='a', 'x'..='z']).to_string(),
+            "[ax-z]"
+        );
+        assert_eq!(
+            Expected::Bytes(b"\x89PNG\r\n").to_string(),
+            "b\"\\x89PNG\\r\\n\""
+        );
+        assert_eq!(Expected::Label("value").to_string(), "value");
+        assert_eq!(Expected::End.to_string(), "end of input");
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod test_alloc {
+    use crate::parsers::branch::alternative;
+    use crate::parsers::characters::{decimal_digit1, read_char, read_text};
+    use crate::parsers::sequence::tuple;
+    use crate::ParserInput;
+
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let mut reader = ParserInput::new("ab");
+        let first = ParserError::new_expected(reader.save_cursor(), Expected::Char('x'));
+        let second = ParserError::new_expected(reader.save_cursor(), Expected::Char('y'));
+        reader.read();
+        let third = ParserError::new_expected(reader.save_cursor(), Expected::Char('z'));
+
+        let error = first.clone().merge(second.clone());
+        assert_eq!(
+            error.expected(),
+            &[Expected::Char('x'), Expected::Char('y')]
+        );
+
+        let error = error.merge(first);
+        assert_eq!(
+            error.expected(),
+            &[Expected::Char('x'), Expected::Char('y')]
+        );
+
+        let error = error.merge(third.clone());
+        assert_eq!(error, third);
+
+        let error = third.clone().merge(second);
+        assert_eq!(error, third);
+    }
+
+    #[test]
+    fn test_display() {
+        let reader = ParserInput::new("");
+        let mut error = ParserError::new(reader.save_cursor());
+        assert_eq!(error.to_string(), "unexpected input at 1:1");
+
+        error.add_expected(Expected::Text("true"));
+        assert_eq!(error.to_string(), "expected `true` at 1:1");
+
+        error.add_expected(Expected::Text("false"));
+        error.add_expected(Expected::Text("null"));
+        assert_eq!(
+            error.to_string(),
+            "expected one of `true`, `false`, `null` at 1:1"
+        );
+    }
+
+    #[test]
+    fn test_tracking() {
+        let mut reader = ParserInput::new("[1, nul]");
+        let mut parser = tuple((
+            read_char('['),
+            decimal_digit1,
+            read_text(", "),
+            alternative((read_text("true"), read_text("false"), read_text("null"))),
+        ));
+
+        let result = parser(&mut reader);
+        assert!(result.is_err());
+        assert!(reader.furthest_error().is_none());
+
+        reader.set_expected_tracking(true);
+        let result = parser(&mut reader);
+        assert!(result.is_err());
+        assert_eq!(
+            reader.furthest_error().unwrap().to_string(),
+            "expected one of `true`, `false`, `null` at 1:5"
+        );
+
+        let mut reader = ParserInput::new("[a]");
+        reader.set_expected_tracking(true);
+        let result = parser(&mut reader);
+        assert!(result.is_err());
+        assert_eq!(
+            reader.take_furthest_error().unwrap().to_string(),
+            "expected [0-9] at 1:2"
+        );
+        assert!(reader.furthest_error().is_none());
+    }
+}
@@ -0,0 +1,141 @@
+use crate::ParserInput;
+
+impl<'a, C, Err> ParserInput<'a, Err, C, [u8]> {
+    // METHODS ----------------------------------------------------------------
+
+    /// Consumes the next byte if present moving the start index forward.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new_bytes(b"\x01\x02");
+    /// assert_eq!(reader.read_byte(), Some(1));
+    /// assert_eq!(reader.read_byte(), Some(2));
+    /// assert_eq!(reader.read_byte(), None);
+    /// ```
+    pub fn read_byte(&mut self) -> Option<u8> {
+        match self.peek_byte() {
+            Some(v) => {
+                self.consume(1);
+                Some(v)
+            }
+            None => None,
+        }
+    }
+
+    /// Consumes the next `count` bytes if present moving the start index forward.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new_bytes(b"\x01\x02\x03");
+    /// assert_eq!(reader.read_bytes(2), Some(&b"\x01\x02"[..]));
+    /// assert_eq!(reader.read_bytes(2), None);
+    /// assert_eq!(reader.read_bytes(1), Some(&b"\x03"[..]));
+    /// ```
+    pub fn read_bytes(&mut self, count: usize) -> Option<&'a [u8]> {
+        match self.peek_bytes(count) {
+            Some(result) => {
+                self.consume(count);
+                Some(result)
+            }
+            None => None,
+        }
+    }
+
+    /// Consumes the next bytes if match `tag` moving the start index forward.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new_bytes(b"\x89PNG");
+    /// assert!(reader.read_tag(b"\x89P"));
+    /// assert!(!reader.read_tag(b"PNG"));
+    /// assert!(reader.read_tag(b"NG"));
+    /// ```
+    pub fn read_tag(&mut self, tag: &[u8]) -> bool {
+        if self.peek_tag(tag) {
+            self.consume(tag.len());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Gets the next byte if present. This method does not consume the byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new_bytes(b"\x01");
+    /// assert_eq!(reader.peek_byte(), Some(1));
+    /// assert_eq!(reader.read_byte(), Some(1));
+    /// assert_eq!(reader.peek_byte(), None);
+    /// ```
+    pub fn peek_byte(&self) -> Option<u8> {
+        self.remaining_content().first().copied()
+    }
+
+    /// Gets the next `count` bytes if present. This method does not consume the bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new_bytes(b"\x01\x02");
+    /// assert_eq!(reader.peek_bytes(2), Some(&b"\x01\x02"[..]));
+    /// assert_eq!(reader.peek_bytes(3), None);
+    /// assert_eq!(reader.byte_offset(), 0);
+    /// ```
+    pub fn peek_bytes(&self, count: usize) -> Option<&'a [u8]> {
+        self.remaining_content().get(..count)
+    }
+
+    /// Gets whether the next bytes match `tag`. This method does not consume the bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new_bytes(b"\x89PNG");
+    /// assert!(reader.peek_tag(b"\x89PNG"));
+    /// assert!(!reader.peek_tag(b"PNG"));
+    /// ```
+    pub fn peek_tag(&self, tag: &[u8]) -> bool {
+        self.remaining_content().starts_with(tag)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_consume_bytes() {
+        let mut reader = ParserInput::new_bytes(b"ab\ncd");
+        assert_eq!(reader.read_bytes(4), Some(&b"ab\nc"[..]));
+
+        assert_eq!(reader.byte_offset(), 4, "The offset is incorrect");
+        assert_eq!(reader.char_offset(), 4, "The char_offset is incorrect");
+        assert_eq!(reader.line(), 1, "The line is incorrect");
+        assert_eq!(reader.column(), 5, "The column is incorrect");
+
+        let cursor = reader.save_cursor();
+        assert_eq!(reader.read_bytes(1), Some(&b"d"[..]));
+
+        let span = reader.substring_to_current(&cursor);
+        assert_eq!(span.content(), &b"d"[..]);
+        assert_eq!(span.start_cursor().byte_offset(), 4);
+        assert_eq!(span.end_cursor().byte_offset(), 5);
+        assert_eq!(span.content_before(), &b"ab\nc"[..]);
+        assert!(reader.is_end());
+    }
+}
@@ -0,0 +1,228 @@
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use bytecount::num_chars;
+#[cfg(feature = "alloc")]
+use memchr::memchr_iter;
+use memchr::memrchr;
+
+use crate::input::{Cursor, PositionPolicy};
+
+/// The content that can be read by a [ParserInput](crate::ParserInput).
+///
+/// It is implemented for text (`str`), where the offsets are measured in bytes,
+/// and for slices (`[T]`, e.g. bytes), where the offsets are measured in elements.
+pub trait InputContent {
+    /// The length of the content.
+    fn len(&self) -> usize;
+
+    /// Whether the content is empty or not.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the fragment of the content between both offsets.
+    fn slice(&self, from: usize, to: usize) -> &Self;
+
+    /// Builds the `Cursor` placed at `offset` moving `cursor` forward following `policy`.
+    fn advance_cursor(&self, cursor: &Cursor, offset: usize, policy: &PositionPolicy) -> Cursor;
+
+    /// The length of the element (a char for text) that starts at `offset` if any.
+    fn element_length(&self, offset: usize) -> Option<usize>;
+
+    /// Whether `offset` is placed between two elements, i.e. in a char boundary for text,
+    /// or at the end of the content.
+    fn is_boundary(&self, offset: usize) -> bool {
+        offset <= self.len()
+    }
+
+    /// The cursors placed at the start of every line of the content following `policy`.
+    /// By default the content has a single line.
+    #[cfg(feature = "alloc")]
+    #[allow(unused_variables)]
+    fn line_starts(&self, policy: &PositionPolicy) -> Vec<Cursor> {
+        vec![Cursor::new(0, 0, 1, 1)]
+    }
+}
+
+/// The length in bytes under which a fragment is walked byte by byte to advance a cursor.
+const SHORT_FRAGMENT_LENGTH: usize = 16;
+
+/// Whether `byte` continues a UTF-8 encoded char instead of starting it.
+#[inline]
+fn is_utf8_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
+impl InputContent for str {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn slice(&self, from: usize, to: usize) -> &Self {
+        &self[from..to]
+    }
+
+    #[inline]
+    fn advance_cursor(&self, cursor: &Cursor, offset: usize, policy: &PositionPolicy) -> Cursor {
+        if *policy != PositionPolicy::new() {
+            return advance_str_cursor(self, cursor, offset, policy);
+        }
+
+        // Speed up method for the default policy: only '\n' breaks lines and every
+        // char takes one column.
+        let bytes = &self.as_bytes()[cursor.byte_offset()..offset];
+
+        // Short fragments, e.g. single chars, are faster without the SIMD setup.
+        if bytes.len() < SHORT_FRAGMENT_LENGTH {
+            let mut line = cursor.line();
+            let mut column = cursor.column();
+            let mut char_offset = cursor.char_offset();
+
+            for &byte in bytes {
+                if byte == b'\n' {
+                    line += 1;
+                    column = 1;
+                } else if !is_utf8_continuation(byte) {
+                    column += 1;
+                } else {
+                    continue;
+                }
+
+                char_offset += 1;
+            }
+
+            return Cursor::new(offset, char_offset, line, column).with_file_id(cursor.file_id());
+        }
+
+        let chars = num_chars(bytes);
+        let (line, column) = match memrchr(b'\n', bytes) {
+            Some(index) => (
+                cursor.line() + bytecount::count(bytes, b'\n'),
+                1 + num_chars(&bytes[index + 1..]),
+            ),
+            None => (cursor.line(), cursor.column() + chars),
+        };
+
+        Cursor::new(offset, cursor.char_offset() + chars, line, column)
+            .with_file_id(cursor.file_id())
+    }
+
+    #[inline]
+    fn element_length(&self, offset: usize) -> Option<usize> {
+        self[offset..].chars().next().map(char::len_utf8)
+    }
+
+    #[inline]
+    fn is_boundary(&self, offset: usize) -> bool {
+        self.is_char_boundary(offset)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn line_starts(&self, policy: &PositionPolicy) -> Vec<Cursor> {
+        let mut result = vec![Cursor::new(0, 0, 1, 1)];
+
+        if policy.only_line_feed() {
+            let mut byte_offset = 0;
+            let mut char_offset = 0;
+
+            for index in memchr_iter(b'\n', self.as_bytes()) {
+                char_offset += num_chars(&self.as_bytes()[byte_offset..=index]);
+                byte_offset = index + 1;
+                result.push(Cursor::new(byte_offset, char_offset, result.len() + 1, 1));
+            }
+        } else {
+            let joins_crlf = policy.joins_crlf();
+            let mut after_cr = false;
+
+            for (char_offset, (index, char)) in self.char_indices().enumerate() {
+                if char == '\n' && after_cr {
+                    // Second part of a CRLF line break.
+                    let start = result.last_mut().unwrap();
+                    *start = Cursor::new(index + 1, char_offset + 1, start.line(), 1);
+                } else if policy.is_line_break(char) {
+                    let byte_offset = index + char.len_utf8();
+                    result.push(Cursor::new(
+                        byte_offset,
+                        char_offset + 1,
+                        result.len() + 1,
+                        1,
+                    ));
+                }
+
+                after_cr = joins_crlf && char == '\r';
+            }
+        }
+
+        result
+    }
+}
+
+impl<T> InputContent for [T] {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn slice(&self, from: usize, to: usize) -> &Self {
+        &self[from..to]
+    }
+
+    fn advance_cursor(&self, cursor: &Cursor, offset: usize, _policy: &PositionPolicy) -> Cursor {
+        let count = offset - cursor.byte_offset();
+
+        Cursor::new(
+            offset,
+            cursor.char_offset() + count,
+            cursor.line(),
+            cursor.column() + count,
+        )
+        .with_file_id(cursor.file_id())
+    }
+
+    #[inline]
+    fn element_length(&self, offset: usize) -> Option<usize> {
+        if offset < self.len() {
+            Some(1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Builds the `Cursor` placed at `offset` moving `cursor` forward through `content`
+/// char by char following `policy`.
+fn advance_str_cursor(
+    content: &str,
+    cursor: &Cursor,
+    offset: usize,
+    policy: &PositionPolicy,
+) -> Cursor {
+    let consumed_fragment = &content[cursor.byte_offset()..offset];
+    let mut column = cursor.column();
+    let mut line = cursor.line();
+    let mut char_offset = cursor.char_offset();
+    let joins_crlf = policy.joins_crlf();
+    let mut after_cr = joins_crlf && content[..cursor.byte_offset()].ends_with('\r');
+
+    for char in consumed_fragment.chars() {
+        if char == '\n' && after_cr {
+            // Second part of a CRLF line break.
+        } else if policy.is_line_break(char) {
+            column = 1;
+            line += 1;
+        } else {
+            column = policy.next_column(column, char);
+        }
+
+        after_cr = joins_crlf && char == '\r';
+        char_offset += 1;
+    }
+
+    Cursor::new(offset, char_offset, line, column).with_file_id(cursor.file_id())
+}
@@ -1,32 +1,83 @@
+use core::fmt;
+
+use crate::input::{InputContent, PositionPolicy};
+
+/// The identifier of a source file.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FileId(u32);
+
+impl FileId {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `FileId` with the specified identifier.
+    pub const fn new(id: u32) -> FileId {
+        FileId(id)
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The identifier.
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+}
+
 /// A specific position inside a `Reader`.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// When the reader uses lazy position tracking, the cursors only contain the byte offset.
+/// See [ParserInput::set_lazy_tracking()](crate::ParserInput::set_lazy_tracking).
+///
+/// It also contains the file of the reader it belongs to. See [SourceMap](crate::SourceMap).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Cursor {
     byte_offset: usize,
     char_offset: usize,
-    line: usize,
-    column: usize,
+    line: u32,
+    column: u32,
+    file_id: FileId,
+    lazy: bool,
 }
 
 impl Cursor {
     // CONSTRUCTORS -----------------------------------------------------------
 
-    /// Builds a new `Cursor` with the specified data.
-    pub(in crate::input) fn new(
-        byte_offset: usize,
-        char_offset: usize,
-        line: usize,
-        column: usize,
-    ) -> Cursor {
+    /// Builds a new `Cursor` with the specified data that belongs to the default file.
+    /// The cursor is never lazy, even if `line` is 0. The line and column saturate
+    /// at `u32::MAX`.
+    ///
+    /// **Note**: the data is not checked against any content, use [LineIndex](crate::LineIndex)
+    /// to build a valid `Cursor` from a byte offset.
+    pub fn new(byte_offset: usize, char_offset: usize, line: usize, column: usize) -> Cursor {
         Cursor {
             byte_offset,
             char_offset,
-            line,
-            column,
+            line: saturate(line),
+            column: saturate(column),
+            file_id: FileId::default(),
+            lazy: false,
+        }
+    }
+
+    /// Builds a new lazy `Cursor` that only contains the byte offset.
+    pub(in crate::input) fn new_lazy(file_id: FileId, byte_offset: usize) -> Cursor {
+        Cursor {
+            byte_offset,
+            char_offset: 0,
+            line: 0,
+            column: 0,
+            file_id,
+            lazy: true,
         }
     }
 
     // GETTERS ----------------------------------------------------------------
 
+    /// The file the `Cursor` belongs to.
+    #[inline]
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
     /// The position of the `Cursor` in bytes.
     #[inline]
     pub fn byte_offset(&self) -> usize {
@@ -34,22 +85,108 @@ impl Cursor {
     }
 
     /// The position of the `Cursor` in characters.
+    /// It is 0 for lazy cursors.
     #[inline]
     pub fn char_offset(&self) -> usize {
         self.char_offset
     }
 
     /// The line number in which the `Cursor` is placed.
-    /// It starts at line 1.
+    /// It starts at line 1, or is 0 for lazy cursors.
     #[inline]
     pub fn line(&self) -> usize {
-        self.line
+        self.line as usize
     }
 
     /// The column number in which the `Cursor` is placed.
-    /// It starts at column 1.
+    /// It starts at column 1, or is 0 for lazy cursors.
     #[inline]
     pub fn column(&self) -> usize {
-        self.column
+        self.column as usize
+    }
+
+    /// Whether the `Cursor` only contains the byte offset or not.
+    #[inline]
+    pub fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets the file the `Cursor` belongs to.
+    pub fn with_file_id(mut self, file_id: FileId) -> Cursor {
+        self.file_id = file_id;
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Computes the whole position of a lazy `Cursor` walking `content` from the start
+    /// following `policy`.
+    /// Non-lazy cursors are returned as they are.
+    ///
+    /// Prefer [ParserInput::resolve_cursor()](crate::ParserInput::resolve_cursor) that
+    /// starts walking from the beginning of the line.
+    ///
+    /// **Note**: `content` must be the one the `Cursor` belongs to.
+    pub fn resolve<I: InputContent + ?Sized>(
+        &self,
+        content: &I,
+        policy: &PositionPolicy,
+    ) -> Cursor {
+        if self.is_lazy() {
+            let start = Cursor::new(0, 0, 1, 1).with_file_id(self.file_id);
+            content.advance_cursor(&start, self.byte_offset, policy)
+        } else {
+            self.clone()
+        }
     }
 }
+
+/// Converts a line or column to its stored size.
+#[inline]
+fn saturate(value: usize) -> u32 {
+    if value > u32::MAX as usize {
+        u32::MAX
+    } else {
+        value as u32
+    }
+}
+
+/// The reason why a `Cursor` does not belong to a reader.
+/// See [ParserInput::try_restore()](crate::ParserInput::try_restore).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CursorError {
+    /// The cursor belongs to another file.
+    WrongFile { expected: FileId, found: FileId },
+    /// The cursor is placed after the end of the content.
+    OutOfBounds { offset: usize, length: usize },
+    /// The cursor is placed in the middle of an element, e.g. inside a char.
+    NotBoundary { offset: usize },
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CursorError::WrongFile { expected, found } => write!(
+                f,
+                "the cursor belongs to file {} instead of file {}",
+                found.id(),
+                expected.id()
+            ),
+            CursorError::OutOfBounds { offset, length } => write!(
+                f,
+                "the cursor offset {} is out of the content of length {}",
+                offset, length
+            ),
+            CursorError::NotBoundary { offset } => write!(
+                f,
+                "the cursor offset {} is not placed in an element boundary",
+                offset
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CursorError {}
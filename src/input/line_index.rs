@@ -0,0 +1,357 @@
+use alloc::vec::Vec;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::input::{Cursor, InputContent, PositionPolicy, Span};
+
+/// The unit in which the columns of a [LineIndex] are measured.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ColumnUnit {
+    /// UTF-8 bytes.
+    Bytes,
+    /// Unicode scalar values, like [Cursor::column()].
+    Chars,
+    /// UTF-16 code units, like the positions of the Language Server Protocol.
+    Utf16,
+    /// Extended grapheme clusters.
+    Graphemes,
+}
+
+/// An index of the lines of a text to convert byte offsets into line/column
+/// positions and vice versa.
+///
+/// Lines and columns start at 1 like in [Cursor]. The lines are delimited following
+/// a [PositionPolicy] whereas the columns are measured in a [ColumnUnit], so tabs
+/// take always one unit.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ColumnUnit, LineIndex, ParserInput};
+/// let reader = ParserInput::new("let a = 1;\nlet 😀 = 2;");
+/// let index = LineIndex::new(reader.content());
+///
+/// assert_eq!(index.line_column(19, ColumnUnit::Chars), Some((2, 6)));
+/// assert_eq!(index.line_column(19, ColumnUnit::Utf16), Some((2, 7)));
+/// assert_eq!(index.offset(2, 7, ColumnUnit::Utf16), Some(19));
+///
+/// let cursor = index.cursor(19).unwrap();
+/// assert_eq!(cursor.char_offset(), 16);
+/// assert_eq!(cursor.line(), 2);
+/// assert_eq!(cursor.column(), 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    content: &'a str,
+    line_starts: Vec<Cursor>,
+    policy: PositionPolicy,
+}
+
+impl<'a> LineIndex<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `LineIndex` for `content` with the default [PositionPolicy].
+    pub fn new(content: &'a str) -> LineIndex<'a> {
+        LineIndex::new_with_policy(content, PositionPolicy::new())
+    }
+
+    /// Builds a new `LineIndex` for `content` whose lines follow `policy`.
+    /// Use the policy of the [ParserInput](crate::ParserInput) to get the same cursors.
+    pub fn new_with_policy(content: &'a str, policy: PositionPolicy) -> LineIndex<'a> {
+        LineIndex {
+            content,
+            line_starts: content.line_starts(&policy),
+            policy,
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The content the `LineIndex` belongs to.
+    pub fn content(&self) -> &'a str {
+        self.content
+    }
+
+    /// The policy followed by the lines of the `LineIndex`.
+    pub fn position_policy(&self) -> &PositionPolicy {
+        &self.policy
+    }
+
+    /// The number of lines of the content.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The cursor at the start of `line` if it exists.
+    pub fn line_start(&self, line: usize) -> Option<&Cursor> {
+        self.line_starts.get(line.checked_sub(1)?)
+    }
+
+    /// The content of `line` without its line break if it exists.
+    pub fn line_content(&self, line: usize) -> Option<&'a str> {
+        let start = self.line_start(line)?.byte_offset();
+        let end = match self.line_start(line + 1) {
+            Some(v) => {
+                let end = v.byte_offset();
+                let line_break =
+                    if self.content[..end].ends_with("\r\n") && self.policy.joins_crlf() {
+                        2
+                    } else {
+                        self.content[..end].chars().next_back().unwrap().len_utf8()
+                    };
+
+                end - line_break
+            }
+            None => self.content.len(),
+        };
+
+        Some(&self.content[start..end])
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Builds the `Cursor` placed at `byte_offset`.
+    /// Returns `None` if `byte_offset` is out of bounds or not in a char boundary.
+    pub fn cursor(&self, byte_offset: usize) -> Option<Cursor> {
+        if !self.content.is_char_boundary(byte_offset) {
+            return None;
+        }
+
+        let line_start = &self.line_starts[self.line_of(byte_offset) - 1];
+        Some(
+            self.content
+                .advance_cursor(line_start, byte_offset, &self.policy),
+        )
+    }
+
+    /// Computes the line and the column in `unit` of `byte_offset`.
+    /// Returns `None` if `byte_offset` is out of bounds or not in a char boundary.
+    pub fn line_column(&self, byte_offset: usize, unit: ColumnUnit) -> Option<(usize, usize)> {
+        if !self.content.is_char_boundary(byte_offset) {
+            return None;
+        }
+
+        let line = self.line_of(byte_offset);
+        let line_start = self.line_starts[line - 1].byte_offset();
+        let text = &self.content[line_start..byte_offset];
+        let column = match unit {
+            ColumnUnit::Bytes => text.len(),
+            ColumnUnit::Chars => text.chars().count(),
+            ColumnUnit::Utf16 => text.chars().map(char::len_utf16).sum(),
+            ColumnUnit::Graphemes => text.graphemes(true).count(),
+        };
+
+        Some((line, column + 1))
+    }
+
+    /// Computes the byte offset of the position placed at `line` and `column` in `unit`.
+    /// The column can point to the end of the line, i.e. the position of its line break.
+    ///
+    /// Returns `None` if the position is out of the line or, in case of `ColumnUnit::Bytes`
+    /// and `ColumnUnit::Utf16`, if it is in the middle of a char.
+    pub fn offset(&self, line: usize, column: usize, unit: ColumnUnit) -> Option<usize> {
+        let line_start = self.line_start(line)?.byte_offset();
+        let text = self.line_content(line)?;
+        let mut remaining = column.checked_sub(1)?;
+
+        let relative_offset = match unit {
+            ColumnUnit::Bytes => {
+                if !text.is_char_boundary(remaining) {
+                    return None;
+                }
+
+                remaining
+            }
+            ColumnUnit::Chars => match text.char_indices().nth(remaining) {
+                Some((offset, _)) => offset,
+                None if text.chars().count() == remaining => text.len(),
+                None => return None,
+            },
+            ColumnUnit::Utf16 => {
+                let mut offset = 0;
+                for char in text.chars() {
+                    if remaining == 0 {
+                        break;
+                    }
+
+                    remaining = remaining.checked_sub(char.len_utf16())?;
+                    offset += char.len_utf8();
+                }
+
+                if remaining != 0 {
+                    return None;
+                }
+
+                offset
+            }
+            ColumnUnit::Graphemes => match text.grapheme_indices(true).nth(remaining) {
+                Some((offset, _)) => offset,
+                None if text.graphemes(true).count() == remaining => text.len(),
+                None => return None,
+            },
+        };
+
+        Some(line_start + relative_offset)
+    }
+
+    /// Builds the `Cursor` placed at `line` and `column` in `unit`.
+    /// See [LineIndex::offset()].
+    pub fn cursor_at(&self, line: usize, column: usize, unit: ColumnUnit) -> Option<Cursor> {
+        self.cursor(self.offset(line, column, unit)?)
+    }
+
+    /// Computes the start and end positions of `span` as (line, column) pairs in `unit`.
+    /// Returns `None` if any position of `span` is out of bounds or not in a char boundary,
+    /// e.g. because it belongs to another content.
+    ///
+    /// **Note**: `span` must belong to the content of the `LineIndex`.
+    pub fn span_line_columns(
+        &self,
+        span: &Span<'a>,
+        unit: ColumnUnit,
+    ) -> Option<((usize, usize), (usize, usize))> {
+        let start = self.line_column(span.start_cursor().byte_offset(), unit)?;
+        let end = self.line_column(span.end_cursor().byte_offset(), unit)?;
+
+        Some((start, end))
+    }
+
+    /// The line that contains `byte_offset`.
+    pub(crate) fn line_of(&self, byte_offset: usize) -> usize {
+        match self
+            .line_starts
+            .binary_search_by_key(&byte_offset, |v| v.byte_offset())
+        {
+            Ok(v) => v + 1,
+            Err(v) => v,
+        }
+    }
+}
+
+/// Computes the whole position of `cursor` walking from the start of its line,
+/// taken from `line_starts`, i.e. the result of [InputContent::line_starts()].
+pub(crate) fn resolve_from_line_starts<I: InputContent + ?Sized>(
+    content: &I,
+    line_starts: &[Cursor],
+    cursor: &Cursor,
+    policy: &PositionPolicy,
+) -> Cursor {
+    let offset = cursor.byte_offset();
+    let line = match line_starts.binary_search_by_key(&offset, |v| v.byte_offset()) {
+        Ok(v) => v,
+        Err(v) => v - 1,
+    };
+
+    let line_start = line_starts[line].clone().with_file_id(cursor.file_id());
+    content.advance_cursor(&line_start, offset, policy)
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::ParserInput;
+
+    use super::*;
+
+    #[test]
+    fn test_line_column() {
+        let index = LineIndex::new("ab\ncé😀e\u{301}x\n");
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line_content(1), Some("ab"));
+        assert_eq!(index.line_content(2), Some("cé😀e\u{301}x"));
+        assert_eq!(index.line_content(3), Some(""));
+        assert_eq!(index.line_content(4), None);
+
+        // Offset of 'x'.
+        let offset = 13;
+        assert_eq!(index.line_column(offset, ColumnUnit::Bytes), Some((2, 11)));
+        assert_eq!(index.line_column(offset, ColumnUnit::Chars), Some((2, 6)));
+        assert_eq!(index.line_column(offset, ColumnUnit::Utf16), Some((2, 7)));
+        assert_eq!(
+            index.line_column(offset, ColumnUnit::Graphemes),
+            Some((2, 5))
+        );
+
+        assert_eq!(index.line_column(0, ColumnUnit::Chars), Some((1, 1)));
+        assert_eq!(index.line_column(15, ColumnUnit::Chars), Some((3, 1)));
+        assert_eq!(index.line_column(5, ColumnUnit::Chars), None);
+        assert_eq!(index.line_column(16, ColumnUnit::Chars), None);
+    }
+
+    #[test]
+    fn test_offset() {
+        let index = LineIndex::new("ab\ncé😀e\u{301}x\n");
+
+        assert_eq!(index.offset(2, 11, ColumnUnit::Bytes), Some(13));
+        assert_eq!(index.offset(2, 6, ColumnUnit::Chars), Some(13));
+        assert_eq!(index.offset(2, 7, ColumnUnit::Utf16), Some(13));
+        assert_eq!(index.offset(2, 5, ColumnUnit::Graphemes), Some(13));
+
+        // End of line.
+        assert_eq!(index.offset(1, 3, ColumnUnit::Chars), Some(2));
+        assert_eq!(index.offset(2, 6, ColumnUnit::Graphemes), Some(14));
+        assert_eq!(index.offset(3, 1, ColumnUnit::Utf16), Some(15));
+
+        // Invalid positions.
+        assert_eq!(index.offset(1, 4, ColumnUnit::Chars), None);
+        assert_eq!(index.offset(1, 0, ColumnUnit::Chars), None);
+        assert_eq!(index.offset(2, 3, ColumnUnit::Bytes), None);
+        assert_eq!(index.offset(2, 4, ColumnUnit::Utf16), None);
+        assert_eq!(index.offset(4, 1, ColumnUnit::Chars), None);
+    }
+
+    #[test]
+    fn test_policy() {
+        let content = "a\r\nb\rc\u{2028}\td";
+        let policy = PositionPolicy::new()
+            .with_carriage_return(true)
+            .with_unicode_separators(true)
+            .with_tab_width(4);
+        let index = LineIndex::new_with_policy(content, policy);
+
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line_content(1), Some("a"));
+        assert_eq!(index.line_content(2), Some("b"));
+        assert_eq!(index.line_content(3), Some("c"));
+        assert_eq!(index.line_content(4), Some("\td"));
+        assert_eq!(index.line_column(10, ColumnUnit::Chars), Some((4, 2)));
+        assert_eq!(index.offset(4, 2, ColumnUnit::Chars), Some(10));
+
+        let mut reader = ParserInput::new(content);
+        reader.set_position_policy(policy);
+        reader.read_text("a\r\nb\rc\u{2028}\t");
+        assert_eq!(index.cursor(10), Some(reader.save_cursor()));
+        assert_eq!(reader.save_cursor(), Cursor::new(10, 8, 4, 5));
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut reader = ParserInput::new("ab\ncé😀e\u{301}x\n");
+        let index = LineIndex::new(reader.content());
+
+        reader.read_text("ab\ncé😀");
+        assert_eq!(index.cursor(10), Some(reader.save_cursor()));
+        assert_eq!(
+            index.cursor_at(2, 4, ColumnUnit::Chars),
+            Some(reader.save_cursor())
+        );
+        assert_eq!(index.cursor(9), None);
+
+        let init_cursor = reader.save_cursor();
+        reader.read_text("e\u{301}x");
+        let span = reader.substring_to_current(&init_cursor);
+        assert_eq!(
+            index.span_line_columns(&span, ColumnUnit::Utf16),
+            Some(((2, 5), (2, 8)))
+        );
+
+        let mut other = ParserInput::new("ab\ncé😀e\u{301}x and more");
+        other.read_text("ab\ncé😀e\u{301}x and");
+        let span = other.substring_to_current(&init_cursor);
+        assert_eq!(index.span_line_columns(&span, ColumnUnit::Utf16), None);
+    }
+}
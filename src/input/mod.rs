@@ -1,21 +1,78 @@
-use std::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cell::{Cell, OnceCell};
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use bytecount::num_chars;
 
+pub use content::*;
 pub use cursor::*;
+#[cfg(feature = "alloc")]
+pub use line_index::*;
+pub use position_policy::*;
+#[cfg(feature = "alloc")]
+pub use source_map::*;
 pub use span::*;
-
+pub use span_info::*;
+pub use spanned::*;
+#[cfg(feature = "alloc")]
+pub use stream::*;
+pub use token::*;
+pub use trivia::*;
+
+use crate::error::Expected;
+#[cfg(feature = "alloc")]
+use crate::error::ParserError;
 use crate::parsers::Quantifier;
 
+mod bytes;
+mod content;
 mod cursor;
+#[cfg(feature = "alloc")]
+mod line_index;
+mod position_policy;
+#[cfg(feature = "alloc")]
+mod source_map;
 mod span;
-
-/// A text input for the parsers.
-#[derive(Debug, Clone)]
-pub struct ParserInput<'a, Err = (), C = ()> {
-    content: &'a str,
+mod span_info;
+mod spanned;
+#[cfg(feature = "alloc")]
+mod stream;
+mod token;
+mod trivia;
+
+/// The identifier of the next `ParserInput` that requires one.
+#[cfg(feature = "alloc")]
+static NEXT_INPUT_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// An input for the parsers. By default it is text but it can be any [InputContent], e.g. bytes.
+#[derive(Debug)]
+pub struct ParserInput<'a, Err = (), C = (), I: ?Sized = str> {
+    content: &'a I,
     cursor: Cursor,
     context: C,
+    partial: bool,
+    lazy_tracking: bool,
+    position_policy: PositionPolicy,
+    check_cursors: bool,
+    trivia: Trivia,
+    #[cfg(feature = "alloc")]
+    id: Cell<usize>,
+    #[cfg(feature = "alloc")]
+    line_starts: OnceCell<Vec<Cursor>>,
+    #[cfg(feature = "alloc")]
+    track_expected: bool,
+    #[cfg(feature = "alloc")]
+    furthest_error: Option<ParserError<'a>>,
+    #[cfg(feature = "alloc")]
+    errors: Vec<(Cursor, Err)>,
+    #[cfg(feature = "alloc")]
+    collect_comments: bool,
+    #[cfg(feature = "alloc")]
+    comments: Vec<Span<'a, I>>,
     _error: PhantomData<Err>,
 }
 
@@ -24,32 +81,17 @@ impl<'a> ParserInput<'a> {
 
     /// Create a new `Reader` with the specified `content`.
     pub fn new(content: &'a str) -> ParserInput<'a, (), ()> {
-        ParserInput {
-            content,
-            cursor: Cursor::new(0, 0, 1, 1),
-            context: (),
-            _error: PhantomData::default(),
-        }
+        ParserInput::new_with_context_and_error(content, ())
     }
 
     /// Create a new `Reader` with the specified `content` and defining an error type.
     pub fn new_with_error<Err>(content: &'a str) -> ParserInput<'a, Err, ()> {
-        ParserInput {
-            content,
-            cursor: Cursor::new(0, 0, 1, 1),
-            context: (),
-            _error: PhantomData::default(),
-        }
+        ParserInput::new_with_context_and_error(content, ())
     }
 
     /// Create a new `Reader` with the specified `content` and `context`.
     pub fn new_with_context<C>(content: &'a str, context: C) -> ParserInput<'a, (), C> {
-        ParserInput {
-            content,
-            cursor: Cursor::new(0, 0, 1, 1),
-            context,
-            _error: PhantomData::default(),
-        }
+        ParserInput::new_with_context_and_error(content, context)
     }
 }
 
@@ -58,11 +100,91 @@ impl<'a, C, Err> ParserInput<'a, Err, C> {
 
     /// Create a new `Reader` with the specified `content` and `context` and defining an error type.
     pub fn new_with_context_and_error(content: &'a str, context: C) -> ParserInput<'a, Err, C> {
+        ParserInput::from_content(content, context)
+    }
+}
+
+impl<'a> ParserInput<'a, (), (), [u8]> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Create a new `Reader` with the specified bytes as `content`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new_bytes(b"\x01\x02");
+    /// assert_eq!(reader.read_byte(), Some(1));
+    /// ```
+    pub fn new_bytes(content: &'a [u8]) -> ParserInput<'a, (), (), [u8]> {
+        ParserInput::new_bytes_with_context_and_error(content, ())
+    }
+
+    /// Create a new `Reader` with the specified bytes as `content` and defining an error type.
+    pub fn new_bytes_with_error<Err>(content: &'a [u8]) -> ParserInput<'a, Err, (), [u8]> {
+        ParserInput::new_bytes_with_context_and_error(content, ())
+    }
+
+    /// Create a new `Reader` with the specified bytes as `content` and `context`.
+    pub fn new_bytes_with_context<C>(
+        content: &'a [u8],
+        context: C,
+    ) -> ParserInput<'a, (), C, [u8]> {
+        ParserInput::new_bytes_with_context_and_error(content, context)
+    }
+}
+
+impl<'a, C, Err> ParserInput<'a, Err, C, [u8]> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Create a new `Reader` with the specified bytes as `content` and `context` and defining an
+    /// error type.
+    pub fn new_bytes_with_context_and_error(
+        content: &'a [u8],
+        context: C,
+    ) -> ParserInput<'a, Err, C, [u8]> {
+        ParserInput::from_content(content, context)
+    }
+}
+
+impl<'a, C, Err, I: ?Sized> ParserInput<'a, Err, C, I> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Create a new `Reader` with the specified `content` of any type, e.g. tokens, and `context`.
+    /// See [ParserInput::new()] and [ParserInput::new_bytes()] for text and bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::<(), (), [u16]>::from_content(&[1, 2][..], ());
+    /// assert_eq!(reader.remaining_content(), &[1, 2][..]);
+    /// ```
+    pub fn from_content(content: &'a I, context: C) -> ParserInput<'a, Err, C, I> {
         ParserInput {
             content,
             cursor: Cursor::new(0, 0, 1, 1),
             context,
-            _error: PhantomData::default(),
+            partial: false,
+            lazy_tracking: false,
+            position_policy: PositionPolicy::new(),
+            check_cursors: false,
+            trivia: Trivia::new(),
+            #[cfg(feature = "alloc")]
+            id: Cell::new(0),
+            #[cfg(feature = "alloc")]
+            line_starts: OnceCell::new(),
+            #[cfg(feature = "alloc")]
+            track_expected: false,
+            #[cfg(feature = "alloc")]
+            furthest_error: None,
+            #[cfg(feature = "alloc")]
+            errors: Vec::new(),
+            #[cfg(feature = "alloc")]
+            collect_comments: false,
+            #[cfg(feature = "alloc")]
+            comments: Vec::new(),
+            _error: PhantomData,
         }
     }
 
@@ -78,60 +200,757 @@ impl<'a, C, Err> ParserInput<'a, Err, C> {
         &mut self.context
     }
 
-    /// The content of the `Reader`.
-    pub fn content(&self) -> &'a str {
-        self.content
+    /// The content of the `Reader`.
+    pub fn content(&self) -> &'a I {
+        self.content
+    }
+
+    /// The file of the content. See [SourceMap].
+    pub fn file_id(&self) -> FileId {
+        self.cursor.file_id()
+    }
+
+    /// The position of the `Reader` in bytes, or elements for non-text inputs.
+    pub fn byte_offset(&self) -> usize {
+        self.cursor.byte_offset()
+    }
+
+    /// Whether the content is only the part of the input that is available yet or the complete input.
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    /// Whether the positions are computed only when they are inspected or not.
+    pub fn is_lazy_tracking(&self) -> bool {
+        self.lazy_tracking
+    }
+
+    /// The policy followed to compute the line and column of the positions.
+    pub fn position_policy(&self) -> &PositionPolicy {
+        &self.position_policy
+    }
+
+    /// The rules followed to skip the trivia. See [ParserInput::skip_trivia()].
+    pub fn trivia(&self) -> &Trivia {
+        &self.trivia
+    }
+
+    /// Whether the cursors passed to the reader are checked to belong to it or not.
+    /// See [ParserInput::set_cursor_checks()].
+    pub fn is_checking_cursors(&self) -> bool {
+        self.check_cursors
+    }
+
+    /// The identifier of the reader, unique among the readers built from a content.
+    /// Its clones share it because they read the same content.
+    ///
+    /// It is assigned the first time it is required to avoid its cost in the readers
+    /// that do not use it.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn id(&self) -> usize {
+        if self.id.get() == 0 {
+            self.id.set(NEXT_INPUT_ID.fetch_add(1, Ordering::Relaxed));
+        }
+
+        self.id.get()
+    }
+
+    /// Whether the reader records the expectations of the parsers that fail or not.
+    #[cfg(feature = "alloc")]
+    pub fn is_tracking_expected(&self) -> bool {
+        self.track_expected
+    }
+
+    /// The error at the furthest position in which a parser has failed,
+    /// including everything that was expected there.
+    ///
+    /// It is only recorded when the expectation tracking is enabled.
+    #[cfg(feature = "alloc")]
+    pub fn furthest_error(&self) -> Option<&ParserError<'a>> {
+        self.furthest_error.as_ref()
+    }
+
+    /// Whether the reader collects the comments skipped as trivia or not.
+    #[cfg(feature = "alloc")]
+    pub fn is_collecting_comments(&self) -> bool {
+        self.collect_comments
+    }
+
+    /// The comments skipped as trivia so far, in order of appearance.
+    ///
+    /// They are only collected when the comment collection is enabled.
+    #[cfg(feature = "alloc")]
+    pub fn comments(&self) -> &[Span<'a, I>] {
+        &self.comments
+    }
+
+    /// The errors reported so far, usually by recovered parsers.
+    /// See [recover_until()](crate::helpers::recover_until).
+    #[cfg(feature = "alloc")]
+    pub fn errors(&self) -> &[(Cursor, Err)] {
+        &self.errors
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Saves the current `Reader`'s position as a new `Cursor`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new("this test");
+    ///
+    /// assert_eq!(reader.read(), Some('t'));
+    /// assert_eq!(reader.read(), Some('h'));
+    /// let cursor = reader.save_cursor();
+    ///
+    /// assert_eq!(cursor.byte_offset(), 2);
+    /// ```
+    pub fn save_cursor(&self) -> Cursor {
+        self.cursor.clone()
+    }
+
+    /// Sets the file of the content so that every `Cursor` and `Span` of the reader belongs to it.
+    /// See [SourceMap].
+    pub fn set_file_id(&mut self, file_id: FileId) {
+        self.cursor = self.cursor.clone().with_file_id(file_id);
+    }
+
+    /// Marks the content as partial, i.e. more content can come after it.
+    ///
+    /// In partial mode the parsers that reach the end of the content without being able
+    /// to decide their result return [ParserResultError::Incomplete](crate::ParserResultError::Incomplete)
+    /// instead of succeeding or failing. See [StreamingInput] for a driver that feeds the content in chunks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::{Needed, ParserInput, ParserResultError};
+    /// # use jpar::characters::read_text;
+    /// let mut reader = ParserInput::new("tr");
+    /// reader.set_partial(true);
+    ///
+    /// let result = read_text("true")(&mut reader);
+    /// assert_eq!(result, Err(ParserResultError::Incomplete(Needed::Size(2))));
+    ///
+    /// let result = read_text("false")(&mut reader);
+    /// assert_eq!(result, Err(ParserResultError::NotFound));
+    /// ```
+    pub fn set_partial(&mut self, partial: bool) {
+        self.partial = partial;
+    }
+
+    /// Sets the rules followed to skip the trivia. See [ParserInput::skip_trivia()].
+    pub fn set_trivia(&mut self, trivia: Trivia) {
+        self.trivia = trivia;
+    }
+
+    /// Consumes the reader returning its associated context.
+    pub fn into_context(self) -> C {
+        self.context
+    }
+
+    /// Enables or disables the recording of the expectations of the parsers that fail.
+    /// Disabling it also discards the recorded error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// # use jpar::branch::alternative;
+    /// # use jpar::characters::read_text;
+    /// let mut reader = ParserInput::new("maybe");
+    /// reader.set_expected_tracking(true);
+    ///
+    /// let result = alternative((read_text("true"), read_text("false")))(&mut reader);
+    /// assert!(result.is_err());
+    ///
+    /// let error = reader.furthest_error().unwrap();
+    /// assert_eq!(error.to_string(), "expected one of `true`, `false` at 1:1");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn set_expected_tracking(&mut self, enabled: bool) {
+        self.track_expected = enabled;
+
+        if !enabled {
+            self.furthest_error = None;
+        }
+    }
+
+    /// The number of expectations recorded at the current position.
+    pub(crate) fn expected_checkpoint(&self) -> usize {
+        #[cfg(feature = "alloc")]
+        {
+            if let Some(error) = &self.furthest_error {
+                if error.cursor().byte_offset() == self.byte_offset() {
+                    return error.expected().len();
+                }
+            }
+        }
+
+        0
+    }
+
+    /// Discards the expectations recorded at the current position after `checkpoint`.
+    /// See [ParserInput::expected_checkpoint()].
+    #[allow(unused_variables)]
+    pub(crate) fn rollback_expected(&mut self, checkpoint: usize) {
+        #[cfg(feature = "alloc")]
+        {
+            let offset = self.byte_offset();
+            if let Some(error) = &mut self.furthest_error {
+                if error.cursor().byte_offset() == offset {
+                    error.truncate_expected(checkpoint);
+                }
+            }
+        }
+    }
+
+    /// Takes the recorded furthest error leaving the reader without it.
+    #[cfg(feature = "alloc")]
+    pub fn take_furthest_error(&mut self) -> Option<ParserError<'a>> {
+        self.furthest_error.take()
+    }
+
+    /// Reports an `error` placed at `cursor` that does not stop the parsing.
+    ///
+    /// The errors reported inside a branch that is finally not found are discarded
+    /// when an enclosing [alternative()](crate::branch::alternative) or
+    /// [not_found_restore()](crate::helpers::not_found_restore) backtracks.
+    #[cfg(feature = "alloc")]
+    pub fn report_error(&mut self, cursor: Cursor, error: Err) {
+        self.errors.push((cursor, error));
+    }
+
+    /// The number of errors reported so far.
+    pub(crate) fn errors_checkpoint(&self) -> usize {
+        #[cfg(feature = "alloc")]
+        {
+            self.errors.len()
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            0
+        }
+    }
+
+    /// Discards the errors reported after `checkpoint`.
+    /// See [ParserInput::errors_checkpoint()].
+    #[allow(unused_variables)]
+    pub(crate) fn rollback_errors(&mut self, checkpoint: usize) {
+        #[cfg(feature = "alloc")]
+        self.errors.truncate(checkpoint);
+    }
+
+    /// Enables or disables the collection of the comments skipped as trivia, e.g. to keep
+    /// them in a formatter. Disabling it also discards the collected comments.
+    ///
+    /// Restoring the reader to a previous position discards the comments collected after it,
+    /// so only the comments skipped by the successful branches are kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::{ParserInput, Trivia};
+    /// let mut reader = ParserInput::new("# first\n# second\nvalue");
+    /// reader.set_trivia(Trivia::new().with_line_comments(&["#"]));
+    /// reader.set_comment_collecting(true);
+    /// reader.skip_trivia().unwrap();
+    ///
+    /// let comments = reader.take_comments();
+    /// assert_eq!(comments.len(), 2);
+    /// assert_eq!(comments[1].content(), "# second");
+    /// assert_eq!(comments[1].start_cursor().line(), 2);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn set_comment_collecting(&mut self, enabled: bool) {
+        self.collect_comments = enabled;
+
+        if !enabled {
+            self.comments.clear();
+        }
+    }
+
+    /// Takes the collected comments leaving the reader without them.
+    #[cfg(feature = "alloc")]
+    pub fn take_comments(&mut self) -> Vec<Span<'a, I>> {
+        core::mem::take(&mut self.comments)
+    }
+
+    /// Takes the reported errors leaving the reader without them.
+    #[cfg(feature = "alloc")]
+    pub fn take_errors(&mut self) -> Vec<(Cursor, Err)> {
+        core::mem::take(&mut self.errors)
+    }
+}
+
+impl<'a, C, Err, I: InputContent + ?Sized> ParserInput<'a, Err, C, I> {
+    // GETTERS ----------------------------------------------------------------
+
+    /// The position of the `Cursor` in characters.
+    /// It starts at char 0.
+    pub fn char_offset(&self) -> usize {
+        self.resolve_cursor(&self.cursor).char_offset()
+    }
+
+    /// The line number of the current position.
+    /// It starts at line 1.
+    pub fn line(&self) -> usize {
+        self.resolve_cursor(&self.cursor).line()
+    }
+
+    /// The column number of the current position.
+    /// It starts at column 1.
+    pub fn column(&self) -> usize {
+        self.resolve_cursor(&self.cursor).column()
+    }
+
+    /// The remaining content as an `Slice`.
+    pub fn remaining_content(&self) -> &'a I {
+        self.content
+            .slice(self.cursor.byte_offset(), self.content.len())
+    }
+
+    /// The length in bytes, or elements for non-text inputs, of the content that is not already read.
+    pub fn remaining_length(&self) -> usize {
+        self.content.len() - self.cursor.byte_offset()
+    }
+
+    /// Returns an empty `Span` located at the current position.
+    pub fn span_at_offset(&self) -> Span<'a, I> {
+        let cursor = self.resolve_cursor(&self.cursor);
+        Span::new(self.content, cursor.clone(), cursor, self.position_policy)
+    }
+
+    /// Whether the reader is placed at the end of the input or not.
+    pub fn is_end(&self) -> bool {
+        self.cursor.byte_offset() >= self.content.len()
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Enables or disables the lazy position tracking. By default it is disabled.
+    ///
+    /// When it is enabled, reading only moves the byte offset forward so the cursors
+    /// returned by [ParserInput::save_cursor()] are lazy, i.e. they do not contain the
+    /// char offset, line and column. These are computed when a `Span` is built or by
+    /// [ParserInput::resolve_cursor()], starting from an index of the lines that is
+    /// built the first time it is required.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new("this\ntest");
+    /// reader.set_lazy_tracking(true);
+    /// reader.read_text("this\nte");
+    ///
+    /// let cursor = reader.save_cursor();
+    /// assert!(cursor.is_lazy());
+    /// assert_eq!(cursor.line(), 0);
+    ///
+    /// let cursor = reader.resolve_cursor(&cursor);
+    /// assert_eq!(cursor.line(), 2);
+    /// assert_eq!(cursor.column(), 3);
+    /// assert_eq!(reader.line(), 2);
+    /// ```
+    pub fn set_lazy_tracking(&mut self, enabled: bool) {
+        self.lazy_tracking = enabled;
+
+        if !enabled {
+            self.cursor = self.resolve_cursor(&self.cursor);
+        }
+    }
+
+    /// Sets the policy followed to compute the line and column of the positions.
+    /// By default only `'\n'` breaks lines and every char takes one column.
+    ///
+    /// **Note**: the cursors saved before calling this method keep their previous positions.
+    pub fn set_position_policy(&mut self, policy: PositionPolicy) {
+        self.position_policy = policy;
+
+        #[cfg(feature = "alloc")]
+        {
+            self.line_starts = OnceCell::new();
+        }
+
+        if !self.cursor.is_lazy() {
+            self.cursor = Cursor::new_lazy(self.cursor.file_id(), self.cursor.byte_offset());
+
+            if !self.lazy_tracking {
+                self.cursor = self.resolve_cursor(&self.cursor);
+            }
+        }
+    }
+
+    /// Computes the whole position of a lazy `cursor`. Non-lazy cursors are returned as they are.
+    ///
+    /// **Note**: `cursor` must belong to this reader.
+    #[inline]
+    pub fn resolve_cursor(&self, cursor: &Cursor) -> Cursor {
+        if !cursor.is_lazy() {
+            return cursor.clone();
+        }
+
+        self.resolve_lazy_cursor(cursor)
+    }
+
+    /// Computes the whole position of a lazy `cursor`.
+    /// It is kept out of line so that [ParserInput::resolve_cursor()] stays cheap for eager cursors.
+    #[inline(never)]
+    fn resolve_lazy_cursor(&self, cursor: &Cursor) -> Cursor {
+        #[cfg(feature = "alloc")]
+        {
+            let line_starts = self
+                .line_starts
+                .get_or_init(|| self.content.line_starts(&self.position_policy));
+            line_index::resolve_from_line_starts(
+                self.content,
+                line_starts,
+                cursor,
+                &self.position_policy,
+            )
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        cursor.resolve(self.content, &self.position_policy)
+    }
+
+    /// Enables or disables the checks of the cursors passed to [ParserInput::restore()]
+    /// and [ParserInput::substring()], which panic if any cursor does not belong to the
+    /// reader. By default they are disabled.
+    ///
+    /// It is intended to catch cursors used across readers in tests, e.g. with
+    /// `reader.set_cursor_checks(cfg!(debug_assertions))`.
+    ///
+    /// **Note**: only the file, the bounds and the element boundaries are checked, so a cursor
+    /// of another reader of the same file placed at a valid offset is not detected.
+    ///
+    /// # Example
+    ///
+    /// ```should_panic
+    /// # use jpar::ParserInput;
+    /// let mut other = ParserInput::new("ab");
+    /// other.read_text("a");
+    ///
+    /// let mut reader = ParserInput::new("éa");
+    /// reader.set_cursor_checks(true);
+    /// reader.restore(other.save_cursor()); // Panics: offset 1 is inside 'é'.
+    /// ```
+    pub fn set_cursor_checks(&mut self, enabled: bool) {
+        self.check_cursors = enabled;
+    }
+
+    /// Checks that `cursor` belongs to this reader, i.e. it has the same file and it is
+    /// placed inside the content at an element boundary.
+    pub fn check_cursor(&self, cursor: &Cursor) -> Result<(), CursorError> {
+        if cursor.file_id() != self.cursor.file_id() {
+            return Err(CursorError::WrongFile {
+                expected: self.cursor.file_id(),
+                found: cursor.file_id(),
+            });
+        }
+
+        check_offset(self.content, cursor.byte_offset())
+    }
+
+    /// Restores the reader to the specified `Cursor` state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new("this test");
+    /// let cursor = reader.save_cursor();
+    ///
+    /// assert_eq!(reader.byte_offset(), 0);
+    /// assert_eq!(cursor.byte_offset(), 0);
+    ///
+    /// assert_eq!(reader.read(), Some('t'));
+    /// assert_eq!(reader.read(), Some('h'));
+    /// let cursor2 = reader.save_cursor();
+    ///
+    /// assert_eq!(reader.byte_offset(), 2);
+    /// assert_eq!(cursor.byte_offset(), 0);
+    /// assert_eq!(cursor2.byte_offset(), 2);
+    ///
+    /// reader.restore(cursor);
+    ///
+    /// assert_eq!(reader.byte_offset(), 0);
+    /// assert_eq!(cursor2.byte_offset(), 2);
+    /// ```
+    ///
+    /// **Note**: `cursor` must belong to this reader, otherwise the positions and spans
+    /// built afterwards are wrong. It is checked in debug builds for readers with different
+    /// files, e.g. those of a [SourceMap], and always when the cursor checks are enabled.
+    /// See [ParserInput::set_cursor_checks()] and [ParserInput::try_restore()].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cursor checks are enabled and `cursor` does not belong to this reader.
+    #[inline]
+    pub fn restore(&mut self, cursor: Cursor) {
+        if self.check_cursors {
+            if let Err(error) = self.check_cursor(&cursor) {
+                panic!("Invalid cursor: {}", error);
+            }
+        }
+
+        debug_assert_eq!(
+            cursor.file_id(),
+            self.cursor.file_id(),
+            "The cursor belongs to another file"
+        );
+
+        #[cfg(feature = "alloc")]
+        if self.collect_comments {
+            let offset = cursor.byte_offset();
+            while let Some(comment) = self.comments.last() {
+                if comment.start_cursor().byte_offset() < offset {
+                    break;
+                }
+
+                self.comments.pop();
+            }
+        }
+
+        self.cursor = if cursor.is_lazy() && !self.lazy_tracking {
+            self.resolve_cursor(&cursor)
+        } else {
+            cursor
+        };
+    }
+
+    /// Restores the reader to the specified `Cursor` state if it belongs to this reader.
+    /// Otherwise the reader is not modified. See [ParserInput::check_cursor()].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::{CursorError, ParserInput};
+    /// let mut other = ParserInput::new("this test");
+    /// other.read_text("this test");
+    ///
+    /// let mut reader = ParserInput::new("this");
+    /// assert_eq!(
+    ///     reader.try_restore(other.save_cursor()),
+    ///     Err(CursorError::OutOfBounds { offset: 9, length: 4 })
+    /// );
+    /// assert_eq!(reader.byte_offset(), 0);
+    ///
+    /// other.restore(reader.save_cursor());
+    /// reader.read_text("th");
+    /// assert_eq!(reader.try_restore(other.save_cursor()), Ok(()));
+    /// assert_eq!(reader.byte_offset(), 0);
+    /// ```
+    pub fn try_restore(&mut self, cursor: Cursor) -> Result<(), CursorError> {
+        self.check_cursor(&cursor)?;
+        self.restore(cursor);
+
+        Ok(())
+    }
+
+    /// Builds the `Span` delimited by `info` following the policy of the reader.
+    /// Returns `None` if `info` does not delimit a fragment of the content.
+    /// See [SpanInfo::resolve()].
+    ///
+    /// **Note**: `info` must have been taken from this content.
+    pub fn resolve_span_info(&self, info: &SpanInfo) -> Option<Span<'a, I>> {
+        if !info.fits(self.content) {
+            return None;
+        }
+
+        Some(Span::new(
+            self.content,
+            self.resolve_cursor(info.start_cursor()),
+            self.resolve_cursor(info.end_cursor()),
+            self.position_policy,
+        ))
+    }
+
+    /// Builds the [SpanInfo] snapshot of `location`, e.g. a `Cursor` or a `Span`, resolving
+    /// its lazy cursors so that the snapshot has the line and column of its positions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new("ab\ncd");
+    /// reader.set_lazy_tracking(true);
+    /// reader.read_text("ab\nc");
+    ///
+    /// let info = reader.span_info(reader.save_cursor());
+    /// assert_eq!((info.start_line(), info.start_column()), (2, 2));
+    /// ```
+    ///
+    /// **Note**: `location` must belong to this reader.
+    pub fn span_info(&self, location: impl Into<SpanInfo>) -> SpanInfo {
+        let info = location.into();
+        SpanInfo::new(
+            info.file_id(),
+            self.resolve_cursor(info.start_cursor()),
+            self.resolve_cursor(info.end_cursor()),
+        )
+    }
+
+    /// Records that `expected` was expected at the current position.
+    /// Only the expectations at the furthest position are kept.
+    ///
+    /// This method does nothing if the expectation tracking is disabled.
+    #[allow(unused_variables)]
+    pub fn expect(&mut self, expected: Expected<'a>) {
+        #[cfg(feature = "alloc")]
+        {
+            if !self.track_expected {
+                return;
+            }
+
+            let error = ParserError::new_expected(self.resolve_cursor(&self.cursor), expected);
+            self.furthest_error = Some(match self.furthest_error.take() {
+                Some(furthest) => furthest.merge(error),
+                None => error,
+            });
+        }
+    }
+
+    /// Gets a `Span` that contains the susbstring delimited by both (`from`, `to`) cursors.
+    /// The order of the cursors does not matter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new("this test");
+    ///
+    /// assert_eq!(reader.read(), Some('t'));
+    /// assert_eq!(reader.read(), Some('h'));
+    /// let from = reader.save_cursor();
+    ///
+    /// assert_eq!(reader.read(), Some('i'));
+    /// assert_eq!(reader.read(), Some('s'));
+    /// assert_eq!(reader.read(), Some(' '));
+    /// assert_eq!(reader.read(), Some('t'));
+    /// assert_eq!(reader.read(), Some('e'));
+    /// assert_eq!(reader.read(), Some('s'));
+    /// let to = reader.save_cursor();
+    ///
+    /// assert_eq!(reader.substring(&from, &to).content(), "is tes");
+    /// assert_eq!(reader.substring(&to, &from).content(), "is tes");
+    /// ```
+    ///
+    /// **Note**: both cursors must belong to this reader. See [ParserInput::set_cursor_checks()].
+    pub fn substring(&self, from: &Cursor, to: &Cursor) -> Span<'a, I> {
+        if self.check_cursors {
+            for cursor in [from, to] {
+                if let Err(error) = self.check_cursor(cursor) {
+                    panic!("Invalid cursor: {}", error);
+                }
+            }
+        }
+
+        let (from, to) = if from.byte_offset() <= to.byte_offset() {
+            (from, to)
+        } else {
+            (to, from)
+        };
+
+        Span::new(
+            self.content,
+            self.resolve_cursor(from),
+            self.resolve_cursor(to),
+            self.position_policy,
+        )
     }
 
-    /// The position of the `Reader` in bytes.
-    pub fn byte_offset(&self) -> usize {
-        self.cursor.byte_offset()
+    /// Gets a `Span` that contains the susbstring delimited by `cursor` and current cursors.
+    /// The order of the cursors does not matter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new("this test");
+    /// assert_eq!(reader.read(), Some('t'));
+    /// assert_eq!(reader.read(), Some('h'));
+    /// let from = reader.save_cursor();
+    ///
+    /// assert_eq!(reader.read(), Some('i'));
+    /// assert_eq!(reader.read(), Some('s'));
+    /// assert_eq!(reader.read(), Some(' '));
+    /// assert_eq!(reader.read(), Some('t'));
+    /// assert_eq!(reader.read(), Some('e'));
+    /// assert_eq!(reader.read(), Some('s'));
+    ///
+    /// assert_eq!(reader.substring_to_current(&from).content(), "is tes");
+    /// ```
+    pub fn substring_to_current(&self, cursor: &Cursor) -> Span<'a, I> {
+        self.substring(&self.cursor, cursor)
     }
 
-    /// The position of the `Cursor` in characters.
-    /// It starts at char 0.
-    pub fn char_offset(&self) -> usize {
-        self.cursor.char_offset()
-    }
+    /// Gets the content delimited by `cursor` and current cursors like
+    /// [ParserInput::substring_to_current()] but without building a `Span`,
+    /// so lazy cursors are not resolved.
+    pub(crate) fn content_to_current(&self, cursor: &Cursor) -> &'a I {
+        if self.check_cursors {
+            if let Err(error) = self.check_cursor(cursor) {
+                panic!("Invalid cursor: {}", error);
+            }
+        }
 
-    /// The line number of the current position.
-    /// It starts at line 1.
-    pub fn line(&self) -> usize {
-        self.cursor.line()
+        let offset = self.byte_offset();
+        let from = cursor.byte_offset();
+        if from <= offset {
+            self.content.slice(from, offset)
+        } else {
+            self.content.slice(offset, from)
+        }
     }
 
-    /// The column number of the current position.
-    /// It starts at column 1.
-    pub fn column(&self) -> usize {
-        self.cursor.column()
+    /// Consumes the next element, i.e. a char for text, if present moving the start index forward.
+    pub fn skip(&mut self) -> bool {
+        match self.content.element_length(self.byte_offset()) {
+            Some(length) => {
+                self.consume(length);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// The remaining content as an `Slice`.
-    pub fn remaining_content(&self) -> &'a str {
-        &self.content[self.cursor.byte_offset()..]
-    }
+    /// Consumes `count` bytes, or elements for non-text inputs, moving the start index forward.
+    fn consume(&mut self, count: usize) {
+        assert!(
+            self.remaining_length() >= count,
+            "count is greater than end position",
+        );
 
-    /// The length in bytes of the content that is not already read.
-    pub fn remaining_length(&self) -> usize {
-        self.content.len() - self.cursor.byte_offset()
+        // Speed up method.
+        if count == 0 {
+            return;
+        }
+
+        let new_offset = self.byte_offset() + count;
+        if self.lazy_tracking {
+            self.cursor = Cursor::new_lazy(self.cursor.file_id(), new_offset);
+        } else {
+            // Without lazy tracking the cursor of the reader is never lazy.
+            self.cursor =
+                self.content
+                    .advance_cursor(&self.cursor, new_offset, &self.position_policy);
+        }
     }
+}
+
+impl<'a, C, Err> ParserInput<'a, Err, C, str> {
+    // GETTERS ----------------------------------------------------------------
 
     /// The length in characters of the content that is not already read.
     pub fn remaining_char_length(&self) -> usize {
         num_chars(self.remaining_content().as_bytes())
     }
 
-    /// Returns an empty `Span` located at the current position.
-    pub fn span_at_offset(&self) -> Span {
-        let cursor = self.cursor.clone();
-        Span::new(self.content, cursor.clone(), cursor)
-    }
-
-    /// Whether the reader is placed at the end of the input or not.
-    pub fn is_end(&self) -> bool {
-        self.cursor.byte_offset() >= self.content.len()
-    }
-
     // METHODS ----------------------------------------------------------------
 
     /// Consumes the next character if present moving the start index forward.
@@ -441,160 +1260,63 @@ impl<'a, C, Err> ParserInput<'a, Err, C> {
             None
         }
     }
+}
 
-    /// Gets a `Span` that contains the susbstring delimited by both (`from`, `to`) cursors.
-    /// The order of the cursors does not matter.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// # use jpar::ParserInput;
-    /// let mut reader = ParserInput::new("this test");
-    ///
-    /// assert_eq!(reader.read(), Some('t'));
-    /// assert_eq!(reader.read(), Some('h'));
-    /// let from = reader.save_cursor();
-    ///
-    /// assert_eq!(reader.read(), Some('i'));
-    /// assert_eq!(reader.read(), Some('s'));
-    /// assert_eq!(reader.read(), Some(' '));
-    /// assert_eq!(reader.read(), Some('t'));
-    /// assert_eq!(reader.read(), Some('e'));
-    /// assert_eq!(reader.read(), Some('s'));
-    /// let to = reader.save_cursor();
-    ///
-    /// assert_eq!(reader.substring(&from, &to).content(), "is tes");
-    /// assert_eq!(reader.substring(&to, &from).content(), "is tes");
-    /// ```
-    pub fn substring(&self, from: &Cursor, to: &Cursor) -> Span<'a> {
-        let (from, to) = if from.byte_offset() <= to.byte_offset() {
-            (from, to)
-        } else {
-            (to, from)
-        };
-
-        Span::new(self.content, from.clone(), to.clone())
-    }
-
-    /// Gets a `Span` that contains the susbstring delimited by `cursor` and current cursors.
-    /// The order of the cursors does not matter.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// # use jpar::ParserInput;
-    /// let mut reader = ParserInput::new("this test");
-    /// assert_eq!(reader.read(), Some('t'));
-    /// assert_eq!(reader.read(), Some('h'));
-    /// let from = reader.save_cursor();
-    ///
-    /// assert_eq!(reader.read(), Some('i'));
-    /// assert_eq!(reader.read(), Some('s'));
-    /// assert_eq!(reader.read(), Some(' '));
-    /// assert_eq!(reader.read(), Some('t'));
-    /// assert_eq!(reader.read(), Some('e'));
-    /// assert_eq!(reader.read(), Some('s'));
-    ///
-    /// assert_eq!(reader.substring_to_current(&from).content(), "is tes");
-    /// ```
-    pub fn substring_to_current(&self, cursor: &Cursor) -> Span<'a> {
-        self.substring(&self.cursor, cursor)
-    }
+impl<'a, C: Clone> ParserInput<'a, C> {
+    // GETTERS ----------------------------------------------------------------
 
-    /// Saves the current `Reader`'s position as a new `Cursor`.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// # use jpar::ParserInput;
-    /// let mut reader = ParserInput::new("this test");
-    ///
-    /// assert_eq!(reader.read(), Some('t'));
-    /// assert_eq!(reader.read(), Some('h'));
-    /// let cursor = reader.save_cursor();
-    ///
-    /// assert_eq!(cursor.byte_offset(), 2);
-    /// ```
-    pub fn save_cursor(&self) -> Cursor {
-        self.cursor.clone()
-    }
+    /// The remaining content as an `Span`.
+    pub fn remaining_content_span(&self) -> Span<'a> {
+        let cursor = self.resolve_cursor(&self.cursor);
+        let end_cursor =
+            self.content
+                .advance_cursor(&cursor, self.content.len(), &self.position_policy);
 
-    /// Restores the reader to the specified `Cursor` state.
-    ///
-    /// # Safety
-    ///
-    /// This method will cause undefined behaviour if the Cursor was not generated by this reader.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// # use jpar::ParserInput;
-    /// let mut reader = ParserInput::new("this test");
-    /// let cursor = reader.save_cursor();
-    ///
-    /// assert_eq!(reader.byte_offset(), 0);
-    /// assert_eq!(cursor.byte_offset(), 0);
-    ///
-    /// assert_eq!(reader.read(), Some('t'));
-    /// assert_eq!(reader.read(), Some('h'));
-    /// let cursor2 = reader.save_cursor();
-    ///
-    /// assert_eq!(reader.byte_offset(), 2);
-    /// assert_eq!(cursor.byte_offset(), 0);
-    /// assert_eq!(cursor2.byte_offset(), 2);
-    ///
-    /// reader.restore(cursor);
-    ///
-    /// assert_eq!(reader.byte_offset(), 0);
-    /// assert_eq!(cursor2.byte_offset(), 2);
-    /// ```
-    pub fn restore(&mut self, cursor: Cursor) {
-        self.cursor = cursor;
+        Span::new(self.content, cursor, end_cursor, self.position_policy)
     }
+}
 
-    /// Consumes `count` bytes moving the start index forward.
-    fn consume(&mut self, count: usize) {
-        assert!(
-            self.remaining_length() >= count,
-            "count is greater than end position",
-        );
-
-        // Speed up method.
-        if count == 0 {
-            return;
-        }
-
-        let offset = self.byte_offset();
-        let new_offset = offset + count;
-        let consumed_fragment = &self.content[offset..new_offset];
-        let mut column = self.cursor.column();
-        let mut line = self.cursor.line();
-        let mut char_offset = self.cursor.char_offset();
-
-        for char in consumed_fragment.chars() {
-            if char == '\n' {
-                column = 1;
-                line += 1;
-            } else {
-                column += 1;
-            }
-
-            char_offset += 1;
+impl<'a, Err: Clone, C: Clone, I: ?Sized> Clone for ParserInput<'a, Err, C, I> {
+    fn clone(&self) -> Self {
+        ParserInput {
+            content: self.content,
+            cursor: self.cursor.clone(),
+            context: self.context.clone(),
+            partial: self.partial,
+            lazy_tracking: self.lazy_tracking,
+            position_policy: self.position_policy,
+            check_cursors: self.check_cursors,
+            trivia: self.trivia,
+            #[cfg(feature = "alloc")]
+            id: Cell::new(self.id()),
+            #[cfg(feature = "alloc")]
+            line_starts: self.line_starts.clone(),
+            #[cfg(feature = "alloc")]
+            track_expected: self.track_expected,
+            #[cfg(feature = "alloc")]
+            furthest_error: self.furthest_error.clone(),
+            #[cfg(feature = "alloc")]
+            errors: self.errors.clone(),
+            #[cfg(feature = "alloc")]
+            collect_comments: self.collect_comments,
+            #[cfg(feature = "alloc")]
+            comments: self.comments.clone(),
+            _error: PhantomData,
         }
-
-        self.cursor = Cursor::new(new_offset, char_offset, line, column);
     }
 }
 
-impl<'a, C: Clone> ParserInput<'a, C> {
-    // GETTERS ----------------------------------------------------------------
-
-    /// The remaining content as an `Span`.
-    pub fn remaining_content_span(&self) -> Span<'a> {
-        let mut aux_reader = self.clone();
-        aux_reader.consume(self.remaining_length());
-
-        Span::new(self.content, self.cursor.clone(), aux_reader.cursor)
+/// Checks that `offset` is placed inside `content` at an element boundary.
+fn check_offset<I: InputContent + ?Sized>(content: &I, offset: usize) -> Result<(), CursorError> {
+    if offset > content.len() {
+        Err(CursorError::OutOfBounds {
+            offset,
+            length: content.len(),
+        })
+    } else if !content.is_boundary(offset) {
+        Err(CursorError::NotBoundary { offset })
+    } else {
+        Ok(())
     }
 }
 
@@ -655,4 +1377,163 @@ mod tests {
         assert_eq!(reader.line(), 1, "The line is incorrect");
         assert_eq!(reader.column(), 2, "The column is incorrect");
     }
+
+    #[test]
+    fn test_consume_long_fragment() {
+        let text = "This\nis\nthe\nfragment モスフェト\nwith\nseveral lines";
+        let mut reader = ParserInput::new(text);
+        reader.consume(37);
+
+        assert_eq!(reader.byte_offset(), 37, "The offset is incorrect");
+        assert_eq!(reader.char_offset(), 27, "The char_offset is incorrect");
+        assert_eq!(reader.line(), 5, "The line is incorrect");
+        assert_eq!(reader.column(), 1, "The column is incorrect");
+
+        reader.consume(text.len() - 37);
+
+        assert_eq!(reader.char_offset(), 45, "The char_offset is incorrect");
+        assert_eq!(reader.line(), 6, "The line is incorrect");
+        assert_eq!(reader.column(), 14, "The column is incorrect");
+    }
+
+    #[test]
+    fn test_lazy_tracking() {
+        let text = "This\nis\nthe\nfragmentモスフェト";
+        let mut reader = ParserInput::new(text);
+        reader.set_lazy_tracking(true);
+        reader.consume(7);
+
+        let cursor = reader.save_cursor();
+        assert!(cursor.is_lazy(), "The cursor must be lazy");
+        assert_eq!(cursor.byte_offset(), 7, "The offset is incorrect");
+        assert_eq!(reader.char_offset(), 7, "The char_offset is incorrect");
+        assert_eq!(reader.line(), 2, "The line is incorrect");
+        assert_eq!(reader.column(), 3, "The column is incorrect");
+
+        reader.consume(22);
+
+        assert_eq!(reader.byte_offset(), 29, "The offset is incorrect");
+        assert_eq!(reader.char_offset(), 23, "The char_offset is incorrect");
+        assert_eq!(reader.line(), 4, "The line is incorrect");
+        assert_eq!(reader.column(), 12, "The column is incorrect");
+
+        let span = reader.substring_to_current(&cursor);
+        assert_eq!(span.content(), "\nthe\nfragmentモスフ");
+        assert_eq!(span.start_cursor(), &Cursor::new(7, 7, 2, 3));
+        assert_eq!(span.end_cursor(), &Cursor::new(29, 23, 4, 12));
+
+        reader.set_lazy_tracking(false);
+        assert!(
+            !reader.save_cursor().is_lazy(),
+            "The cursor must be resolved"
+        );
+
+        reader.consume(3);
+        assert_eq!(reader.save_cursor(), Cursor::new(32, 24, 4, 13));
+
+        // Built cursors are never lazy.
+        let cursor = Cursor::new(7, 7, 0, 3);
+        assert!(!cursor.is_lazy(), "The cursor must not be lazy");
+        assert_eq!(cursor.resolve(text, &PositionPolicy::new()), cursor);
+    }
+
+    #[test]
+    fn test_position_policy() {
+        let text = "a\r\n\tb\r\rc\u{2028}d";
+        let mut reader = ParserInput::new(text);
+        reader.read_text("a\r\n\tb\r\rc\u{2028}");
+
+        assert_eq!(reader.line(), 2, "The line is incorrect");
+        assert_eq!(reader.column(), 7, "The column is incorrect");
+
+        let policy = PositionPolicy::new()
+            .with_carriage_return(true)
+            .with_unicode_separators(true)
+            .with_tab_width(4);
+        reader.set_position_policy(policy);
+
+        assert_eq!(reader.line(), 5, "The line is incorrect");
+        assert_eq!(reader.column(), 1, "The column is incorrect");
+
+        reader.set_position_policy(policy.with_crlf_as_one(false));
+
+        assert_eq!(reader.line(), 6, "The line is incorrect");
+        assert_eq!(reader.column(), 1, "The column is incorrect");
+
+        // Lazy tracking.
+        let mut reader = ParserInput::new(text);
+        reader.set_position_policy(policy);
+        reader.set_lazy_tracking(true);
+        reader.read_text("a\r");
+        let cursor = reader.save_cursor();
+        reader.read_text("\n\tb");
+
+        assert_eq!(reader.line(), 2, "The line is incorrect");
+        assert_eq!(reader.column(), 6, "The column is incorrect");
+
+        let span = reader.substring_to_current(&cursor);
+        assert_eq!(span.start_cursor(), &Cursor::new(2, 2, 2, 1));
+        assert_eq!(span.lines(), "\n\tb");
+    }
+
+    #[test]
+    fn test_check_cursor() {
+        let mut other = ParserInput::new("ab\ncdefg");
+        other.read_text("a");
+        let inside_char = other.save_cursor();
+        other.read_text("b\ncdefg");
+        let out_of_bounds = other.save_cursor();
+
+        let mut reader = ParserInput::new("é😀");
+        reader.read_text("é");
+        let valid = reader.save_cursor();
+
+        assert_eq!(reader.check_cursor(&valid), Ok(()));
+        assert_eq!(
+            reader.check_cursor(&inside_char),
+            Err(CursorError::NotBoundary { offset: 1 })
+        );
+        assert_eq!(
+            reader.check_cursor(&out_of_bounds),
+            Err(CursorError::OutOfBounds {
+                offset: 8,
+                length: 6
+            })
+        );
+        assert_eq!(
+            reader.check_cursor(&valid.clone().with_file_id(FileId::new(2))),
+            Err(CursorError::WrongFile {
+                expected: FileId::default(),
+                found: FileId::new(2)
+            })
+        );
+
+        assert!(reader.try_restore(inside_char).is_err());
+        assert_eq!(reader.save_cursor(), valid);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid cursor")]
+    fn test_cursor_checks() {
+        let mut other = ParserInput::new("abcdef");
+        other.read_text("abcdef");
+
+        let mut reader = ParserInput::new("abc");
+        reader.set_cursor_checks(true);
+        assert!(reader.is_checking_cursors());
+
+        let cursor = reader.save_cursor();
+        reader.substring(&cursor, &other.save_cursor());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid cursor: the cursor belongs to")]
+    fn test_cursor_checks_restore_other_file() {
+        let mut reader = ParserInput::new("abc");
+        reader.set_cursor_checks(true);
+        reader.read_text("a");
+
+        let cursor = reader.save_cursor().with_file_id(FileId::new(2));
+        reader.restore(cursor);
+    }
 }
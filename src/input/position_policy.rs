@@ -0,0 +1,130 @@
+/// The rules used to compute the line and column of the positions of a text.
+///
+/// By default only `'\n'` breaks lines and every char, including tabs, takes one column.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, PositionPolicy};
+/// let mut reader = ParserInput::new("a\r\n\tb\rc");
+/// reader.set_position_policy(
+///     PositionPolicy::new()
+///         .with_carriage_return(true)
+///         .with_tab_width(4),
+/// );
+///
+/// reader.read_text("a\r\n\t");
+/// assert_eq!(reader.line(), 2);
+/// assert_eq!(reader.column(), 5);
+///
+/// reader.read_text("b\r");
+/// assert_eq!(reader.line(), 3);
+/// assert_eq!(reader.column(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PositionPolicy {
+    carriage_return: bool,
+    unicode_separators: bool,
+    crlf_as_one: bool,
+    tab_width: usize,
+}
+
+impl PositionPolicy {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `PositionPolicy` with the default rules.
+    pub const fn new() -> PositionPolicy {
+        PositionPolicy {
+            carriage_return: false,
+            unicode_separators: false,
+            crlf_as_one: true,
+            tab_width: 1,
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Whether a lone `'\r'` breaks lines or not.
+    pub fn carriage_return(&self) -> bool {
+        self.carriage_return
+    }
+
+    /// Whether the unicode separators `'\u{85}'`, `'\u{2028}'` and `'\u{2029}'` break lines or not.
+    pub fn unicode_separators(&self) -> bool {
+        self.unicode_separators
+    }
+
+    /// Whether `"\r\n"` is a single line break or two when `'\r'` breaks lines.
+    pub fn crlf_as_one(&self) -> bool {
+        self.crlf_as_one
+    }
+
+    /// The number of columns between two tab stops.
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets whether a lone `'\r'` breaks lines or not.
+    pub fn with_carriage_return(mut self, enabled: bool) -> PositionPolicy {
+        self.carriage_return = enabled;
+        self
+    }
+
+    /// Sets whether the unicode separators `'\u{85}'`, `'\u{2028}'` and `'\u{2029}'` break lines or not.
+    pub fn with_unicode_separators(mut self, enabled: bool) -> PositionPolicy {
+        self.unicode_separators = enabled;
+        self
+    }
+
+    /// Sets whether `"\r\n"` is a single line break or two when `'\r'` breaks lines.
+    pub fn with_crlf_as_one(mut self, enabled: bool) -> PositionPolicy {
+        self.crlf_as_one = enabled;
+        self
+    }
+
+    /// Sets the number of columns between two tab stops.
+    /// A tab moves the column to the next tab stop. A width of 0 is treated as 1.
+    pub fn with_tab_width(mut self, width: usize) -> PositionPolicy {
+        self.tab_width = width;
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Whether `char` breaks lines or not.
+    pub fn is_line_break(&self, char: char) -> bool {
+        match char {
+            '\n' => true,
+            '\r' => self.carriage_return,
+            '\u{85}' | '\u{2028}' | '\u{2029}' => self.unicode_separators,
+            _ => false,
+        }
+    }
+
+    /// Whether the only line break is `'\n'` or not.
+    pub(crate) fn only_line_feed(&self) -> bool {
+        !self.carriage_return && !self.unicode_separators
+    }
+
+    /// Whether a `'\n'` that follows a `'\r'` is part of the same line break or not.
+    pub(crate) fn joins_crlf(&self) -> bool {
+        self.carriage_return && self.crlf_as_one
+    }
+
+    /// The column after `char` when it is placed at `column`.
+    pub(crate) fn next_column(&self, column: usize, char: char) -> usize {
+        if char == '\t' && self.tab_width > 1 {
+            ((column - 1) / self.tab_width + 1) * self.tab_width + 1
+        } else {
+            column + 1
+        }
+    }
+}
+
+impl Default for PositionPolicy {
+    fn default() -> Self {
+        PositionPolicy::new()
+    }
+}
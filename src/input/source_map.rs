@@ -0,0 +1,336 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::OnceCell;
+use core::convert::TryFrom;
+
+use crate::diagnostic::Diagnostic;
+use crate::input::line_index::resolve_from_line_starts;
+use crate::input::{
+    Cursor, FileId, InputContent, LineIndex, ParserInput, PositionPolicy, Span, SpanInfo,
+};
+
+/// A source file owned by a [SourceMap].
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    id: FileId,
+    path: String,
+    content: String,
+    line_starts: OnceCell<Vec<Cursor>>,
+}
+
+impl SourceFile {
+    // GETTERS ----------------------------------------------------------------
+
+    /// The identifier of the file.
+    pub fn id(&self) -> FileId {
+        self.id
+    }
+
+    /// The path of the file.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The content of the file.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// A database of source files that hands out readers whose cursors and spans are stamped
+/// with the identifier of their file, so they can be resolved back to their file later.
+///
+/// The files are identified from 1 because [FileId::default()] is used by the readers that
+/// do not belong to any `SourceMap`.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{SourceMap, SpanInfo};
+/// let mut sources = SourceMap::new();
+/// let main = sources.add("main.src", "include lib;");
+/// let lib = sources.add("lib.src", "let a = 1;\nlet b = c;");
+///
+/// let info = {
+///     let mut reader = sources.input(lib).unwrap();
+///     reader.read_text("let a = 1;\nlet b = ");
+///     let start = reader.save_cursor();
+///     reader.read_text("c");
+///     SpanInfo::from(reader.substring_to_current(&start))
+/// };
+///
+/// assert_eq!(info.file_id(), lib);
+/// assert_eq!(sources.get(info.file_id()).unwrap().path(), "lib.src");
+/// assert_eq!(sources.lines(&info), Some("let b = c;"));
+/// assert_eq!(
+///     sources.diagnostic("unknown variable", &info).unwrap().to_string(),
+///     "error: unknown variable\n --> lib.src:2:9\n  |\n2 | let b = c;\n  |         ^\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+    position_policy: PositionPolicy,
+}
+
+impl SourceMap {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new empty `SourceMap`.
+    pub fn new() -> SourceMap {
+        SourceMap::default()
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The number of files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether there is any file or not.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// The files in the order they were added.
+    pub fn files(&self) -> &[SourceFile] {
+        &self.files
+    }
+
+    /// The policy followed to compute the positions of every file.
+    pub fn position_policy(&self) -> &PositionPolicy {
+        &self.position_policy
+    }
+
+    /// The file identified by `id` if it belongs to the `SourceMap`.
+    pub fn get(&self, id: FileId) -> Option<&SourceFile> {
+        self.files.get((id.id() as usize).checked_sub(1)?)
+    }
+
+    /// The first file whose path is `path` if any.
+    pub fn find(&self, path: &str) -> Option<&SourceFile> {
+        self.files.iter().find(|v| v.path == path)
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets the policy followed to compute the positions of every file.
+    pub fn set_position_policy(&mut self, policy: PositionPolicy) {
+        self.position_policy = policy;
+
+        for file in &mut self.files {
+            file.line_starts = OnceCell::new();
+        }
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Adds a new file returning its identifier.
+    pub fn add(&mut self, path: impl Into<String>, content: impl Into<String>) -> FileId {
+        let id = u32::try_from(self.files.len() + 1).expect("Too many files in the SourceMap");
+        let id = FileId::new(id);
+        self.files.push(SourceFile {
+            id,
+            path: path.into(),
+            content: content.into(),
+            line_starts: OnceCell::new(),
+        });
+
+        id
+    }
+
+    /// Builds a reader over the file identified by `id`.
+    pub fn input(&self, id: FileId) -> Option<ParserInput<'_>> {
+        self.input_with_context_and_error(id, ())
+    }
+
+    /// Builds a reader over the file identified by `id` with the specified `context`
+    /// and defining an error type.
+    pub fn input_with_context_and_error<Err, C>(
+        &self,
+        id: FileId,
+        context: C,
+    ) -> Option<ParserInput<'_, Err, C>> {
+        let file = self.get(id)?;
+        let mut reader = ParserInput::new_with_context_and_error(file.content(), context);
+        reader.set_file_id(id);
+        reader.set_position_policy(self.position_policy);
+
+        Some(reader)
+    }
+
+    /// Builds a `LineIndex` of the file identified by `id`.
+    pub fn line_index(&self, id: FileId) -> Option<LineIndex<'_>> {
+        let file = self.get(id)?;
+        Some(LineIndex::new_with_policy(
+            file.content(),
+            self.position_policy,
+        ))
+    }
+
+    /// Builds the `Span` delimited by `info` inside its file.
+    /// Returns `None` if the file does not belong to the `SourceMap` or `info` is out of it.
+    ///
+    /// The lines of every file are indexed the first time they are required to resolve
+    /// lazy cursors.
+    pub fn span(&self, info: &SpanInfo) -> Option<Span<'_>> {
+        let file = self.get(info.file_id())?;
+        let content = file.content();
+        if !info.fits(content) {
+            return None;
+        }
+
+        Some(Span::new(
+            content,
+            self.resolve_cursor(file, info.start_cursor()),
+            self.resolve_cursor(file, info.end_cursor()),
+            self.position_policy,
+        ))
+    }
+
+    /// Computes the whole position of a lazy `cursor` of `file`.
+    /// Non-lazy cursors are returned as they are.
+    fn resolve_cursor(&self, file: &SourceFile, cursor: &Cursor) -> Cursor {
+        if !cursor.is_lazy() {
+            return cursor.clone();
+        }
+
+        let line_starts = file
+            .line_starts
+            .get_or_init(|| file.content.line_starts(&self.position_policy));
+        resolve_from_line_starts(file.content(), line_starts, cursor, &self.position_policy)
+    }
+
+    /// The text of the lines that contain `info`. See [Span::lines()].
+    pub fn lines(&self, info: &SpanInfo) -> Option<&str> {
+        Some(self.span(info)?.lines())
+    }
+
+    /// Builds a `Diagnostic` pointing to `info` that includes the path of its file.
+    pub fn diagnostic(
+        &self,
+        message: impl Into<String>,
+        info: &SpanInfo,
+    ) -> Option<Diagnostic<'_>> {
+        let path = self.get(info.file_id())?.path();
+        Some(Diagnostic::new(message, self.span(info)?).with_path(path))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_files() {
+        let mut sources = SourceMap::new();
+        assert!(sources.is_empty());
+
+        let a = sources.add("a.txt", "first");
+        let b = sources.add("b.txt", String::from("second"));
+
+        assert_eq!(sources.len(), 2);
+        assert_eq!(a, FileId::new(1));
+        assert_eq!(b, FileId::new(2));
+        assert_eq!(sources.get(b).unwrap().content(), "second");
+        assert_eq!(sources.find("a.txt").unwrap().id(), a);
+        assert!(sources.get(FileId::default()).is_none());
+        assert!(sources.find("c.txt").is_none());
+        assert!(sources.input(FileId::new(3)).is_none());
+    }
+
+    #[test]
+    fn test_stamped_cursors() {
+        let mut sources = SourceMap::new();
+        let a = sources.add("a.txt", "ab\ncd");
+        let b = sources.add("b.txt", "ab\ncd");
+
+        let mut reader = sources.input(b).unwrap();
+        assert_eq!(reader.file_id(), b);
+        reader.read_text("ab\nc");
+
+        let cursor = reader.save_cursor();
+        assert_eq!(cursor.file_id(), b);
+        assert_eq!(cursor, Cursor::new(4, 4, 2, 2).with_file_id(b));
+
+        reader.set_lazy_tracking(true);
+        reader.read_text("d");
+        let span = reader.substring_to_current(&cursor);
+        assert_eq!(span.file_id(), b);
+        assert_eq!(span.end_cursor().file_id(), b);
+
+        let info = SpanInfo::from(&span);
+        assert_eq!(info.file_id(), b);
+        assert_eq!(sources.span(&info), Some(span));
+        assert_eq!(sources.lines(&info), Some("cd"));
+        assert!(sources.span(&info.clone().with_file_id(a)).is_some());
+        assert!(sources.span(&info.with_file_id(FileId::new(3))).is_none());
+    }
+
+    #[test]
+    fn test_span_lazy() {
+        let mut sources = SourceMap::new();
+        sources.set_position_policy(PositionPolicy::new().with_tab_width(4));
+        let a = sources.add("a.txt", "ab\n\tcd\nef");
+
+        let info = SpanInfo::new(a, Cursor::new_lazy(a, 4), Cursor::new_lazy(a, 8));
+
+        let span = sources.span(&info).unwrap();
+        assert_eq!(span.content(), "cd\ne");
+        assert_eq!(
+            span.start_cursor(),
+            &Cursor::new(4, 4, 2, 5).with_file_id(a)
+        );
+        assert_eq!(span.end_cursor(), &Cursor::new(8, 8, 3, 2).with_file_id(a));
+
+        // The indexed lines follow the changes of the policy.
+        sources.set_position_policy(PositionPolicy::new());
+        let span = sources.span(&info).unwrap();
+        assert_eq!(
+            span.start_cursor(),
+            &Cursor::new(4, 4, 2, 2).with_file_id(a)
+        );
+
+        let info = SpanInfo::new(a, info.start_cursor().clone(), Cursor::new(12, 12, 3, 5));
+        assert!(sources.span(&info).is_none());
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let mut sources = SourceMap::new();
+        sources.set_position_policy(PositionPolicy::new().with_carriage_return(true));
+        let a = sources.add("a.txt", "a\rb\rc");
+
+        let mut reader = sources.input(a).unwrap();
+        reader.read_text("a\rb\r");
+        let cursor = reader.save_cursor();
+        reader.read_text("c");
+        let info = SpanInfo::from(&reader.substring_to_current(&cursor));
+
+        assert_eq!(
+            sources.diagnostic("unknown", &info).unwrap().render(),
+            "error: unknown\n --> a.txt:3:1\n  |\n3 | c\n  | ^\n"
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "The cursor belongs to another file")]
+    fn test_restore_wrong_input() {
+        let mut sources = SourceMap::new();
+        let a = sources.add("a.txt", "abc");
+        let b = sources.add("b.txt", "abc");
+
+        let mut reader_a = sources.input(a).unwrap();
+        let mut reader_b = sources.input(b).unwrap();
+        reader_a.read_text("ab");
+        reader_b.restore(reader_a.save_cursor());
+    }
+}
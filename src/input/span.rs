@@ -1,51 +1,71 @@
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::{Bound, RangeBounds};
+
 use memchr::{memchr, memrchr};
 
-use crate::input::Cursor;
+use crate::input::{Cursor, FileId, InputContent, PositionPolicy};
 
 /// A Span is a set of meta information about the location of a substring.
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Span<'a> {
-    content: &'a str,
+///
+/// Two spans are equal if they delimit the same range of the same content in the same file.
+/// They are ordered by their file, then by their start position and then by their end
+/// position, and only their file and range are hashed, so they can be used as keys of maps.
+#[derive(Debug)]
+pub struct Span<'a, I: ?Sized = str> {
+    content: &'a I,
     start_cursor: Cursor,
     end_cursor: Cursor,
+    policy: PositionPolicy,
 }
 
-impl<'a> Span<'a> {
+impl<'a, I: InputContent + ?Sized> Span<'a, I> {
     // CONSTRUCTORS -----------------------------------------------------------
 
-    /// Builds a new `Span` with the specified data.
-    pub(in crate::input) fn new(
-        content: &'a str,
+    /// Builds a new `Span` with the specified data whose positions follow `policy`.
+    pub(crate) fn new(
+        content: &'a I,
         start_cursor: Cursor,
         end_cursor: Cursor,
-    ) -> Span {
+        policy: PositionPolicy,
+    ) -> Span<'a, I> {
         Span {
             content,
             start_cursor,
             end_cursor,
+            policy,
         }
     }
 
     // GETTERS ----------------------------------------------------------------
 
     /// The whole content the `Span` belongs to.
-    pub fn whole_content(&self) -> &'a str {
+    pub fn whole_content(&self) -> &'a I {
         self.content
     }
 
     /// The content of the `Span`.
-    pub fn content(&self) -> &'a str {
-        &self.content[self.start_cursor.byte_offset()..self.end_cursor.byte_offset()]
+    pub fn content(&self) -> &'a I {
+        self.content.slice(
+            self.start_cursor.byte_offset(),
+            self.end_cursor.byte_offset(),
+        )
     }
 
     /// The content before the `Span`.
-    pub fn content_before(&self) -> &'a str {
-        &self.content[..self.start_cursor.byte_offset()]
+    pub fn content_before(&self) -> &'a I {
+        self.content.slice(0, self.start_cursor.byte_offset())
     }
 
     /// The content after the `Span`.
-    pub fn content_after(&self) -> &'a str {
-        &self.content[self.end_cursor.byte_offset()..]
+    pub fn content_after(&self) -> &'a I {
+        self.content
+            .slice(self.end_cursor.byte_offset(), self.content.len())
+    }
+
+    /// The file the `Span` belongs to. See [SourceMap](crate::SourceMap).
+    pub fn file_id(&self) -> FileId {
+        self.start_cursor.file_id()
     }
 
     /// The start position of the `Span` in bytes.
@@ -58,6 +78,11 @@ impl<'a> Span<'a> {
         &self.end_cursor
     }
 
+    /// The policy followed by the positions of the `Span`.
+    pub fn position_policy(&self) -> &PositionPolicy {
+        &self.policy
+    }
+
     /// The length of the `Span` in bytes.
     pub fn len(&self) -> usize {
         self.end_cursor.byte_offset() - self.start_cursor.byte_offset()
@@ -73,8 +98,206 @@ impl<'a> Span<'a> {
         self.end_cursor.char_offset() - self.start_cursor.char_offset()
     }
 
+    // METHODS ----------------------------------------------------------------
+
+    /// Whether `cursor` is placed inside the `Span` or not.
+    /// The end of the `Span` is not included, so an empty `Span` does not contain any position.
+    pub fn contains(&self, cursor: &Cursor) -> bool {
+        let offset = cursor.byte_offset();
+        self.start_cursor.byte_offset() <= offset && offset < self.end_cursor.byte_offset()
+    }
+
+    /// Whether `other` is completely placed inside the `Span` or not.
+    ///
+    /// **Note**: both spans must belong to the same content.
+    pub fn contains_span(&self, other: &Span<'a, I>) -> bool {
+        self.check_same_content(other);
+        self.start_cursor.byte_offset() <= other.start_cursor.byte_offset()
+            && other.end_cursor.byte_offset() <= self.end_cursor.byte_offset()
+    }
+
+    /// Builds the smallest `Span` that contains both spans, including the gap between them.
+    ///
+    /// **Note**: both spans must belong to the same content.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new("let a = 3;");
+    /// let start = reader.save_cursor();
+    /// reader.read_text("let");
+    /// let keyword = reader.substring_to_current(&start);
+    ///
+    /// reader.read_text(" a = ");
+    /// let start = reader.save_cursor();
+    /// reader.read_text("3");
+    /// let value = reader.substring_to_current(&start);
+    ///
+    /// assert_eq!(keyword.merge(&value).content(), "let a = 3");
+    /// assert_eq!(value.merge(&keyword).content(), "let a = 3");
+    /// assert_eq!(keyword.join(&value), None);
+    /// assert_eq!(keyword.gap(&value).unwrap().content(), " a = ");
+    /// ```
+    pub fn merge(&self, other: &Span<'a, I>) -> Span<'a, I> {
+        self.check_same_content(other);
+
+        let start_cursor = if self.start_cursor.byte_offset() <= other.start_cursor.byte_offset() {
+            &self.start_cursor
+        } else {
+            &other.start_cursor
+        };
+
+        let end_cursor = if self.end_cursor.byte_offset() >= other.end_cursor.byte_offset() {
+            &self.end_cursor
+        } else {
+            &other.end_cursor
+        };
+
+        Span::new(
+            self.content,
+            start_cursor.clone(),
+            end_cursor.clone(),
+            self.policy,
+        )
+    }
+
+    /// Builds the `Span` that contains both spans if they overlap or are adjacent.
+    /// Returns `None` if there is a gap between them.
+    ///
+    /// **Note**: both spans must belong to the same content.
+    pub fn join(&self, other: &Span<'a, I>) -> Option<Span<'a, I>> {
+        match self.gap(other) {
+            Some(gap) if !gap.is_empty() => None,
+            _ => Some(self.merge(other)),
+        }
+    }
+
+    /// Builds the `Span` that contains the content between both spans.
+    /// Returns `None` if they overlap. Adjacent spans have an empty gap.
+    ///
+    /// **Note**: both spans must belong to the same content.
+    pub fn gap(&self, other: &Span<'a, I>) -> Option<Span<'a, I>> {
+        self.check_same_content(other);
+
+        let (first, second) = if self.start_cursor.byte_offset() <= other.start_cursor.byte_offset()
+        {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        if first.end_cursor.byte_offset() > second.start_cursor.byte_offset() {
+            return None;
+        }
+
+        Some(Span::new(
+            self.content,
+            first.end_cursor.clone(),
+            second.start_cursor.clone(),
+            self.policy,
+        ))
+    }
+
+    /// Builds the `Span` that is contained by both spans.
+    /// Returns `None` if there is a gap between them. Adjacent spans intersect in an empty `Span`.
+    ///
+    /// **Note**: both spans must belong to the same content.
+    pub fn intersection(&self, other: &Span<'a, I>) -> Option<Span<'a, I>> {
+        self.check_same_content(other);
+
+        let start_cursor = if self.start_cursor.byte_offset() >= other.start_cursor.byte_offset() {
+            &self.start_cursor
+        } else {
+            &other.start_cursor
+        };
+
+        let end_cursor = if self.end_cursor.byte_offset() <= other.end_cursor.byte_offset() {
+            &self.end_cursor
+        } else {
+            &other.end_cursor
+        };
+
+        if start_cursor.byte_offset() > end_cursor.byte_offset() {
+            return None;
+        }
+
+        Some(Span::new(
+            self.content,
+            start_cursor.clone(),
+            end_cursor.clone(),
+            self.policy,
+        ))
+    }
+
+    /// Builds the `Span` delimited by `range`, whose offsets are relative to the start of this `Span`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of the `Span`, is decreasing or, for text, its bounds are
+    /// not in char boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::ParserInput;
+    /// let mut reader = ParserInput::new("let a\nbc = 3;");
+    /// reader.read_text("let ");
+    /// let start = reader.save_cursor();
+    /// reader.read_text("a\nbc");
+    /// let span = reader.substring_to_current(&start);
+    ///
+    /// let sub_span = span.sub_span(2..);
+    /// assert_eq!(sub_span.content(), "bc");
+    /// assert_eq!(sub_span.start_cursor().line(), 2);
+    /// assert_eq!(sub_span.start_cursor().column(), 1);
+    /// ```
+    pub fn sub_span(&self, range: impl RangeBounds<usize>) -> Span<'a, I> {
+        let start = match range.start_bound() {
+            Bound::Included(v) => *v,
+            Bound::Excluded(v) => *v + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(v) => *v + 1,
+            Bound::Excluded(v) => *v,
+            Bound::Unbounded => self.len(),
+        };
+
+        assert!(start <= end, "The range is decreasing");
+        assert!(end <= self.len(), "The range is out of the span");
+
+        // Check the bounds.
+        self.content().slice(start, end);
+
+        let start_offset = self.start_cursor.byte_offset();
+        let start_cursor =
+            self.content
+                .advance_cursor(&self.start_cursor, start_offset + start, &self.policy);
+        let end_cursor =
+            self.content
+                .advance_cursor(&start_cursor, start_offset + end, &self.policy);
+
+        Span::new(self.content, start_cursor, end_cursor, self.policy)
+    }
+
+    /// Checks that both spans belong to the same content.
+    #[inline]
+    fn check_same_content(&self, other: &Span<'a, I>) {
+        debug_assert!(
+            core::ptr::eq(self.content, other.content),
+            "The spans belong to different contents"
+        );
+    }
+}
+
+impl<'a> Span<'a> {
+    // GETTERS ----------------------------------------------------------------
+
     /// Returns the line(s) in which the `Span` is contained.
     /// If it is composed of more than one line, the result will be all the lines.
+    /// The line breaks are those of its [PositionPolicy].
     ///
     /// # Example
     ///
@@ -93,12 +316,27 @@ impl<'a> Span<'a> {
     /// assert_eq!(span.lines(), "This\nis\nthe");
     /// ```
     pub fn lines(&self) -> &'a str {
-        let start_index = match memrchr(b'\n', self.content_before().as_bytes()) {
-            Some(v) => v + 1,
+        if self.policy.only_line_feed() {
+            let start_index = match memrchr(b'\n', self.content_before().as_bytes()) {
+                Some(v) => v + 1,
+                None => 0,
+            };
+
+            let end_index = match memchr(b'\n', self.content_after().as_bytes()) {
+                Some(v) => v + self.end_cursor.byte_offset(),
+                None => self.content.len(),
+            };
+
+            return &self.content[start_index..end_index];
+        }
+
+        let content_before = self.content_before();
+        let start_index = match content_before.rfind(|c| self.policy.is_line_break(c)) {
+            Some(v) => v + content_before[v..].chars().next().unwrap().len_utf8(),
             None => 0,
         };
 
-        let end_index = match memchr(b'\n', self.content_after().as_bytes()) {
+        let end_index = match self.content_after().find(|c| self.policy.is_line_break(c)) {
             Some(v) => v + self.end_cursor.byte_offset(),
             None => self.content.len(),
         };
@@ -107,12 +345,75 @@ impl<'a> Span<'a> {
     }
 }
 
+impl<'a, I: PartialEq + ?Sized> PartialEq for Span<'a, I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.start_cursor.file_id() == other.start_cursor.file_id()
+            && self.start_cursor.byte_offset() == other.start_cursor.byte_offset()
+            && self.end_cursor.byte_offset() == other.end_cursor.byte_offset()
+            && (core::ptr::eq(self.content, other.content) || self.content == other.content)
+    }
+}
+
+impl<'a, I: Eq + ?Sized> Eq for Span<'a, I> {}
+
+impl<'a, I: Ord + ?Sized> PartialOrd for Span<'a, I> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, I: Ord + ?Sized> Ord for Span<'a, I> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start_cursor
+            .file_id()
+            .cmp(&other.start_cursor.file_id())
+            .then_with(|| {
+                self.start_cursor
+                    .byte_offset()
+                    .cmp(&other.start_cursor.byte_offset())
+            })
+            .then_with(|| {
+                self.end_cursor
+                    .byte_offset()
+                    .cmp(&other.end_cursor.byte_offset())
+            })
+            .then_with(|| {
+                if core::ptr::eq(self.content, other.content) {
+                    Ordering::Equal
+                } else {
+                    self.content.cmp(other.content)
+                }
+            })
+    }
+}
+
+impl<'a, I: ?Sized> Hash for Span<'a, I> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.start_cursor.file_id().hash(state);
+        self.start_cursor.byte_offset().hash(state);
+        self.end_cursor.byte_offset().hash(state);
+    }
+}
+
+impl<'a, I: ?Sized> Clone for Span<'a, I> {
+    fn clone(&self) -> Self {
+        Span {
+            content: self.content,
+            start_cursor: self.start_cursor.clone(),
+            end_cursor: self.end_cursor.clone(),
+            policy: self.policy,
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 
 #[cfg(test)]
 mod tests {
+    use crate::ParserInput;
+
     use super::*;
 
     #[test]
@@ -122,6 +423,7 @@ mod tests {
             text,
             Cursor::new(1, 0, 0, 0), // Only offset matters.
             Cursor::new(1, 0, 0, 0), // Only offset matters.
+            PositionPolicy::new(),
         );
 
         assert_eq!(span.lines(), "This", "The lines is incorrect");
@@ -132,6 +434,7 @@ mod tests {
             text,
             Cursor::new(4, 0, 0, 0), // Only offset matters.
             Cursor::new(4, 0, 0, 0), // Only offset matters.
+            PositionPolicy::new(),
         );
 
         assert_eq!(span.lines(), "This", "The lines is incorrect");
@@ -142,6 +445,7 @@ mod tests {
             text,
             Cursor::new(5, 0, 0, 0), // Only offset matters.
             Cursor::new(5, 0, 0, 0), // Only offset matters.
+            PositionPolicy::new(),
         );
 
         assert_eq!(span.lines(), "is", "The lines is incorrect");
@@ -152,10 +456,164 @@ mod tests {
         let text = "This\nis\nthe\ntest";
         let span = Span::new(
             text,
-            Cursor::new(5, 0, 0, 0),  // Only offset matters.
-            Cursor::new(08, 0, 0, 0), // Only offset matters.
+            Cursor::new(5, 0, 0, 0), // Only offset matters.
+            Cursor::new(8, 0, 0, 0), // Only offset matters.
+            PositionPolicy::new(),
         );
 
         assert_eq!(span.lines(), "is\nthe", "The lines is incorrect");
     }
+
+    #[test]
+    fn test_lines_policy() {
+        let text = "This\r\nis\rthe\u{2028}test";
+        let policy = PositionPolicy::new()
+            .with_carriage_return(true)
+            .with_unicode_separators(true);
+        let span = Span::new(
+            text,
+            Cursor::new(6, 0, 0, 0),  // Only offset matters.
+            Cursor::new(10, 0, 0, 0), // Only offset matters.
+            policy,
+        );
+
+        assert_eq!(span.lines(), "is\rthe", "The lines is incorrect");
+
+        let span = Span::new(
+            text,
+            Cursor::new(12, 0, 0, 0), // Only offset matters.
+            Cursor::new(12, 0, 0, 0), // Only offset matters.
+            policy,
+        );
+
+        assert_eq!(span.lines(), "the", "The lines is incorrect");
+
+        let span = Span::new(
+            text,
+            Cursor::new(6, 0, 0, 0), // Only offset matters.
+            Cursor::new(6, 0, 0, 0), // Only offset matters.
+            PositionPolicy::new(),
+        );
+
+        assert_eq!(
+            span.lines(),
+            "is\rthe\u{2028}test",
+            "The lines is incorrect"
+        );
+    }
+
+    #[test]
+    fn test_merge_join_gap() {
+        let mut reader = ParserInput::new("ab cd ef");
+        let a = span_of(&mut reader, "ab");
+        let b = span_of(&mut reader, " cd");
+        let c = span_of(&mut reader, " ef");
+
+        assert_eq!(a.merge(&c).content(), "ab cd ef");
+        assert_eq!(a.merge(&a).content(), "ab");
+        assert_eq!(a.join(&b).unwrap().content(), "ab cd");
+        assert_eq!(b.join(&a).unwrap().content(), "ab cd");
+        assert_eq!(a.join(&c), None);
+        assert_eq!(
+            a.merge(&b).join(&b.merge(&c)).unwrap().content(),
+            "ab cd ef"
+        );
+
+        assert_eq!(a.gap(&c).unwrap().content(), " cd");
+        assert_eq!(c.gap(&a).unwrap().content(), " cd");
+        assert!(a.gap(&b).unwrap().is_empty());
+        assert_eq!(a.merge(&b).gap(&b), None);
+    }
+
+    #[test]
+    fn test_contains_intersection() {
+        let mut reader = ParserInput::new("ab cd ef");
+        let a = span_of(&mut reader, "ab");
+        let b = span_of(&mut reader, " cd");
+        let c = span_of(&mut reader, " ef");
+        let ab = a.merge(&b);
+        let bc = b.merge(&c);
+
+        assert!(ab.contains(a.start_cursor()));
+        assert!(ab.contains(b.start_cursor()));
+        assert!(!ab.contains(b.end_cursor()));
+        assert!(!a.sub_span(1..1).contains(a.start_cursor()));
+        assert!(ab.contains_span(&a));
+        assert!(ab.contains_span(&ab));
+        assert!(!ab.contains_span(&bc));
+
+        assert_eq!(ab.intersection(&bc), Some(b.clone()));
+        assert_eq!(bc.intersection(&ab), Some(b.clone()));
+        assert!(a.intersection(&b).unwrap().is_empty());
+        assert_eq!(a.intersection(&c), None);
+    }
+
+    #[test]
+    fn test_sub_span() {
+        let mut reader = ParserInput::new("xモス\nフェト");
+        reader.read_text("x");
+        let span = span_of(&mut reader, "モス\nフェト");
+
+        let sub_span = span.sub_span(3..=9);
+        assert_eq!(sub_span.content(), "ス\nフ");
+        assert_eq!(sub_span.start_cursor(), &Cursor::new(4, 2, 1, 3));
+        assert_eq!(sub_span.end_cursor(), &Cursor::new(11, 5, 2, 2));
+        assert_eq!(span.sub_span(..), span);
+        assert!(span.sub_span(..0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sub_span_out_of_bounds() {
+        let mut reader = ParserInput::new("abc");
+        let span = span_of(&mut reader, "ab");
+        span.sub_span(1..3);
+    }
+
+    #[test]
+    fn test_ord_hash() {
+        let mut reader = ParserInput::new("ab cd ef");
+        let a = span_of(&mut reader, "ab");
+        let b = span_of(&mut reader, " cd");
+        let ab = a.merge(&b);
+
+        assert!(a < ab);
+        assert!(ab < b);
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(a.clone(), 1);
+        map.insert(ab.clone(), 2);
+        assert_eq!(map.get(&a), Some(&1));
+        assert_eq!(map.get(&b), None);
+
+        let set: std::collections::BTreeSet<_> =
+            vec![b.clone(), ab.clone(), a.clone()].into_iter().collect();
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![a, ab, b]);
+    }
+
+    #[test]
+    fn test_ord_hash_files() {
+        let mut reader = ParserInput::new("ab");
+        reader.set_file_id(FileId::new(2));
+        let first = span_of(&mut reader, "ab");
+
+        let mut reader = ParserInput::new("ab");
+        reader.set_file_id(FileId::new(1));
+        let second = span_of(&mut reader, "ab");
+
+        assert_ne!(first, second);
+        assert!(second < first);
+
+        let set: std::collections::HashSet<_> = vec![first.clone(), second.clone(), first]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    fn span_of<'a>(reader: &mut ParserInput<'a>, text: &str) -> Span<'a> {
+        let start = reader.save_cursor();
+        assert!(reader.read_text(text));
+        reader.substring_to_current(&start)
+    }
 }
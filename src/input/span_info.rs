@@ -0,0 +1,281 @@
+use core::ops::Range;
+
+use crate::input::{Cursor, FileId, InputContent, PositionPolicy, Span};
+
+/// An owned snapshot of a [Span] that does not borrow the content, so it can outlive it
+/// or be sent to other threads.
+///
+/// The snapshot of a lazy `Cursor`, e.g. one saved by a reader with lazy tracking, has no
+/// line and column, so they are reported as 0. Use [ParserInput::span_info()](crate::ParserInput::span_info)
+/// to build snapshots with resolved positions.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{FileId, ParserInput, SpanInfo};
+/// let info = {
+///     let content = String::from("let a\n= 3;");
+///     let mut reader = ParserInput::new(&content);
+///     reader.read_text("let ");
+///     let start = reader.save_cursor();
+///     reader.read_text("a\n=");
+///
+///     SpanInfo::from(&reader.substring_to_current(&start)).with_file_id(FileId::new(3))
+/// };
+///
+/// assert_eq!(info.file_id(), FileId::new(3));
+/// assert_eq!(info.byte_range(), 4..7);
+/// assert_eq!((info.start_line(), info.start_column()), (1, 5));
+/// assert_eq!((info.end_line(), info.end_column()), (2, 2));
+///
+/// let span = info.resolve("let a\n= 3;").unwrap();
+/// assert_eq!(span.content(), "a\n=");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SpanInfo {
+    start_cursor: Cursor,
+    end_cursor: Cursor,
+}
+
+impl SpanInfo {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `SpanInfo` with the specified data.
+    /// The file of both cursors is replaced by `file_id`.
+    pub fn new(file_id: FileId, start_cursor: Cursor, end_cursor: Cursor) -> SpanInfo {
+        SpanInfo {
+            start_cursor: start_cursor.with_file_id(file_id),
+            end_cursor: end_cursor.with_file_id(file_id),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The file the `SpanInfo` belongs to.
+    pub fn file_id(&self) -> FileId {
+        self.start_cursor.file_id()
+    }
+
+    /// The start position.
+    pub fn start_cursor(&self) -> &Cursor {
+        &self.start_cursor
+    }
+
+    /// The end position.
+    pub fn end_cursor(&self) -> &Cursor {
+        &self.end_cursor
+    }
+
+    /// The range of the `SpanInfo` in bytes, or elements for non-text inputs.
+    pub fn byte_range(&self) -> Range<usize> {
+        self.start_cursor.byte_offset()..self.end_cursor.byte_offset()
+    }
+
+    /// The line of the start position. It is 0 for lazy positions.
+    pub fn start_line(&self) -> usize {
+        self.start_cursor.line()
+    }
+
+    /// The column of the start position. It is 0 for lazy positions.
+    pub fn start_column(&self) -> usize {
+        self.start_cursor.column()
+    }
+
+    /// The line of the end position. It is 0 for lazy positions.
+    pub fn end_line(&self) -> usize {
+        self.end_cursor.line()
+    }
+
+    /// The column of the end position. It is 0 for lazy positions.
+    pub fn end_column(&self) -> usize {
+        self.end_cursor.column()
+    }
+
+    /// The length of the `SpanInfo` in bytes, or elements for non-text inputs.
+    /// It is 0 if the end position is before the start one.
+    pub fn len(&self) -> usize {
+        self.end_cursor
+            .byte_offset()
+            .saturating_sub(self.start_cursor.byte_offset())
+    }
+
+    /// Whether the `SpanInfo` is empty or not.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets the file the `SpanInfo` belongs to.
+    pub fn with_file_id(self, file_id: FileId) -> SpanInfo {
+        SpanInfo::new(file_id, self.start_cursor, self.end_cursor)
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Builds the `Span` delimited by the `SpanInfo` inside `content` with the default [PositionPolicy].
+    /// Lazy cursors are resolved against `content`.
+    /// Returns `None` if the `SpanInfo` is out of `content`, its positions are not placed
+    /// at element boundaries, e.g. inside a char, or the end is before the start.
+    ///
+    /// Use [ParserInput::resolve_span_info()](crate::ParserInput::resolve_span_info) to follow
+    /// the policy of a reader.
+    pub fn resolve<'a, I: InputContent + ?Sized>(&self, content: &'a I) -> Option<Span<'a, I>> {
+        if !self.fits(content) {
+            return None;
+        }
+
+        let policy = PositionPolicy::new();
+
+        Some(Span::new(
+            content,
+            self.start_cursor.resolve(content, &policy),
+            self.end_cursor.resolve(content, &policy),
+            policy,
+        ))
+    }
+
+    /// Whether the `SpanInfo` delimits a valid fragment of `content` or not.
+    pub(crate) fn fits<I: InputContent + ?Sized>(&self, content: &I) -> bool {
+        let range = self.byte_range();
+        range.start <= range.end
+            && content.is_boundary(range.start)
+            && content.is_boundary(range.end)
+    }
+}
+
+impl<'a, I: InputContent + ?Sized> From<&Span<'a, I>> for SpanInfo {
+    fn from(span: &Span<'a, I>) -> Self {
+        SpanInfo {
+            start_cursor: span.start_cursor().clone(),
+            end_cursor: span.end_cursor().clone(),
+        }
+    }
+}
+
+impl<'a, I: InputContent + ?Sized> From<Span<'a, I>> for SpanInfo {
+    fn from(span: Span<'a, I>) -> Self {
+        SpanInfo::from(&span)
+    }
+}
+
+impl From<&Cursor> for SpanInfo {
+    fn from(cursor: &Cursor) -> Self {
+        SpanInfo {
+            start_cursor: cursor.clone(),
+            end_cursor: cursor.clone(),
+        }
+    }
+}
+
+impl From<Cursor> for SpanInfo {
+    fn from(cursor: Cursor) -> Self {
+        SpanInfo {
+            start_cursor: cursor.clone(),
+            end_cursor: cursor,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::parsers::characters::read_text;
+    use crate::{ParserInput, ParserResult, ParserResultError};
+
+    use super::*;
+
+    #[test]
+    fn test_from_span_and_cursor() {
+        let mut reader = ParserInput::new("ab\ncd");
+        reader.read_text("a");
+        let start = reader.save_cursor();
+        reader.read_text("b\nc");
+        let span = reader.substring_to_current(&start);
+
+        let info = SpanInfo::from(&span);
+        assert_eq!(info.file_id(), FileId::default());
+        assert_eq!(info.start_cursor(), &Cursor::new(1, 1, 1, 2));
+        assert_eq!(info.end_cursor(), &Cursor::new(4, 4, 2, 2));
+        assert_eq!(info.len(), 3);
+        assert_eq!(info.resolve(reader.content()), Some(span));
+
+        let info = SpanInfo::from(reader.save_cursor());
+        assert!(info.is_empty());
+        assert_eq!(info.byte_range(), 4..4);
+        assert_eq!(info.resolve("abc"), None);
+    }
+
+    #[test]
+    fn test_resolve_invalid() {
+        // Inside a char.
+        let info = SpanInfo::new(
+            FileId::default(),
+            Cursor::new(1, 1, 1, 2),
+            Cursor::new(2, 2, 1, 3),
+        );
+        assert_eq!(info.resolve("éa"), None);
+        assert_eq!(ParserInput::new("éa").resolve_span_info(&info), None);
+        assert!(info.resolve("ab").is_some());
+
+        // Reversed.
+        let info = SpanInfo::new(
+            FileId::default(),
+            Cursor::new(2, 2, 1, 3),
+            Cursor::new(1, 1, 1, 2),
+        );
+        assert_eq!(info.len(), 0);
+        assert_eq!(info.resolve("ab"), None);
+        assert_eq!(ParserInput::new("ab").resolve_span_info(&info), None);
+    }
+
+    #[test]
+    fn test_resolve_lazy() {
+        let mut reader = ParserInput::new("ab\r\ncd");
+        reader.set_position_policy(PositionPolicy::new().with_carriage_return(true));
+        reader.set_lazy_tracking(true);
+        reader.read_text("ab\r\nc");
+
+        let info = SpanInfo::from(reader.save_cursor());
+        assert!(info.start_cursor().is_lazy());
+        assert_eq!((info.start_line(), info.start_column()), (0, 0));
+
+        let span = info.resolve(reader.content()).unwrap();
+        assert_eq!(span.start_cursor(), &Cursor::new(5, 5, 2, 2));
+
+        let span = reader.resolve_span_info(&info).unwrap();
+        assert_eq!(span.start_cursor(), &Cursor::new(5, 5, 2, 2));
+
+        let info = reader.span_info(reader.save_cursor());
+        assert!(!info.start_cursor().is_lazy());
+        assert_eq!(info.start_cursor(), &Cursor::new(5, 5, 2, 2));
+        assert_eq!(info.end_cursor(), &Cursor::new(5, 5, 2, 2));
+
+        let span = reader.substring_to_current(&Cursor::new(0, 0, 1, 1));
+        assert_eq!(reader.span_info(&span), SpanInfo::from(&span));
+    }
+
+    #[test]
+    fn test_error_location() {
+        fn parser(reader: &mut ParserInput<&'static str>) -> ParserResult<(), &'static str> {
+            read_text("a")(reader)?;
+            Err(ParserResultError::Error((reader.save_cursor(), "error")))
+        }
+
+        let info = {
+            let content = String::from("abc");
+            let mut reader = ParserInput::new_with_error(&content);
+            parser(&mut reader)
+                .unwrap_err()
+                .map_location(SpanInfo::from)
+        };
+
+        assert_eq!(
+            info,
+            ParserResultError::Error((SpanInfo::from(Cursor::new(1, 1, 1, 2)), "error"))
+        );
+    }
+}
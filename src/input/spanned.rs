@@ -0,0 +1,101 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::input::Span;
+
+/// A value together with the `Span` of the content it was parsed from.
+/// It dereferences to the value.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, Spanned};
+/// # use jpar::characters::read_text;
+/// # use jpar::helpers::{map_result, spanned};
+/// let mut reader = ParserInput::new("let a");
+/// let mut parser = map_result(spanned(read_text("let")), |_, v| Spanned::from(v));
+///
+/// let keyword = parser(&mut reader).unwrap();
+/// assert_eq!(*keyword, "let");
+/// assert_eq!(keyword.len(), 3);
+/// assert_eq!(keyword.span().start_cursor().column(), 1);
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct Spanned<'a, T, I: ?Sized = str> {
+    value: T,
+    span: Span<'a, I>,
+}
+
+impl<'a, T, I: ?Sized> Spanned<'a, T, I> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `Spanned` with the specified data.
+    pub fn new(value: T, span: Span<'a, I>) -> Spanned<'a, T, I> {
+        Spanned { value, span }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// The value.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// The `Span` of the value.
+    pub fn span(&self) -> &Span<'a, I> {
+        &self.span
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Gets the value discarding the `Span`.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Gets the value and its `Span`.
+    pub fn into_parts(self) -> (T, Span<'a, I>) {
+        (self.value, self.span)
+    }
+
+    /// Maps the value keeping the same `Span`.
+    pub fn map<R>(self, map_fn: impl FnOnce(T) -> R) -> Spanned<'a, R, I> {
+        Spanned {
+            value: map_fn(self.value),
+            span: self.span,
+        }
+    }
+}
+
+impl<'a, T, I: ?Sized> Deref for Spanned<'a, T, I> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<'a, T, I: ?Sized> DerefMut for Spanned<'a, T, I> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<'a, T, I: ?Sized> From<(T, Span<'a, I>)> for Spanned<'a, T, I> {
+    fn from((value, span): (T, Span<'a, I>)) -> Self {
+        Spanned::new(value, span)
+    }
+}
+
+impl<'a, T: Clone, I: ?Sized> Clone for Spanned<'a, T, I> {
+    fn clone(&self) -> Self {
+        Spanned {
+            value: self.value.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use alloc::string::String;
+use core::marker::PhantomData;
+
+use crate::result::ParserResult;
+use crate::{Cursor, InputContent, ParserInput, PositionPolicy, Trivia};
+
+/// An input that is received in chunks, e.g. from a socket or a log stream.
+///
+/// The parsers are executed over the buffered content in partial mode, so they
+/// return [ParserResultError::Incomplete](crate::ParserResultError::Incomplete) when
+/// they need more content to decide. In that case, or when they fail, the position is
+/// not modified so that the parsing can be resumed after appending more content.
+///
+/// The content already parsed is kept in memory until [StreamingInput::discard_consumed()] is called.
+///
+/// **Note**: the parsers cannot return values or errors that borrow the buffered content.
+/// Also, the changes that a parser makes to the context are kept even if it fails or
+/// returns `Incomplete`, so a parser that is resumed must take them into account.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, ParserResult, ParserResultError, StreamingInput};
+/// # use jpar::characters::{read_char, read_none_of0};
+/// # use jpar::sequence::terminated;
+/// # use jpar::verifiers::text_verifier;
+/// fn line(reader: &mut ParserInput) -> ParserResult<String> {
+///     let line = terminated(read_none_of0(text_verifier("\n")), read_char('\n'))(reader)?;
+///     Ok(line.to_string())
+/// }
+///
+/// let mut stream = StreamingInput::new();
+/// stream.push_str("first\nsec");
+/// assert_eq!(stream.parse(line), Ok("first".to_string()));
+/// assert!(stream.parse(line).unwrap_err().is_incomplete());
+///
+/// stream.push_str("ond\n");
+/// assert_eq!(stream.parse(line), Ok("second".to_string()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingInput<Err = (), C = ()> {
+    buffer: String,
+    cursor: Cursor,
+    context: Option<C>,
+    finished: bool,
+    position_policy: PositionPolicy,
+    lazy_tracking: bool,
+    check_cursors: bool,
+    trivia: Trivia,
+    _error: PhantomData<Err>,
+}
+
+impl StreamingInput {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Create a new empty `StreamingInput`.
+    pub fn new() -> StreamingInput<(), ()> {
+        StreamingInput::new_with_context_and_error(())
+    }
+
+    /// Create a new empty `StreamingInput` defining an error type.
+    pub fn new_with_error<Err>() -> StreamingInput<Err, ()> {
+        StreamingInput::new_with_context_and_error(())
+    }
+
+    /// Create a new empty `StreamingInput` with the specified `context`.
+    pub fn new_with_context<C>(context: C) -> StreamingInput<(), C> {
+        StreamingInput::new_with_context_and_error(context)
+    }
+}
+
+impl<Err, C> StreamingInput<Err, C> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Create a new empty `StreamingInput` with the specified `context` and defining an error type.
+    pub fn new_with_context_and_error(context: C) -> StreamingInput<Err, C> {
+        StreamingInput {
+            buffer: String::new(),
+            cursor: Cursor::new(0, 0, 1, 1),
+            context: Some(context),
+            finished: false,
+            position_policy: PositionPolicy::new(),
+            lazy_tracking: false,
+            check_cursors: false,
+            trivia: Trivia::new(),
+            _error: PhantomData,
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The associated context of the `StreamingInput`.
+    pub fn context(&self) -> &C {
+        self.context.as_ref().unwrap()
+    }
+
+    /// The associated context of the `StreamingInput`.
+    pub fn context_mut(&mut self) -> &mut C {
+        self.context.as_mut().unwrap()
+    }
+
+    /// All the content received so far that has not been discarded.
+    pub fn content(&self) -> &str {
+        &self.buffer
+    }
+
+    /// The content received so far that is not already parsed.
+    pub fn remaining_content(&self) -> &str {
+        &self.buffer[self.cursor.byte_offset()..]
+    }
+
+    /// The position from which the next parser will start.
+    ///
+    /// Its byte offset is relative to [StreamingInput::content()], whereas its char offset,
+    /// line and column count from the start of the stream.
+    pub fn cursor(&self) -> &Cursor {
+        &self.cursor
+    }
+
+    /// Whether all the content has been received or not.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The policy followed by the parsers to compute the line and column of the positions.
+    pub fn position_policy(&self) -> &PositionPolicy {
+        &self.position_policy
+    }
+
+    /// Whether the parsers compute the positions only when they are inspected or not.
+    pub fn is_lazy_tracking(&self) -> bool {
+        self.lazy_tracking
+    }
+
+    /// Whether the parsers check that the cursors they use belong to the reader or not.
+    pub fn is_checking_cursors(&self) -> bool {
+        self.check_cursors
+    }
+
+    /// The rules followed by the parsers to skip the trivia.
+    pub fn trivia(&self) -> &Trivia {
+        &self.trivia
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets the policy followed by the parsers to compute the line and column of the positions.
+    /// See [ParserInput::set_position_policy()].
+    ///
+    /// **Note**: the current position keeps its line and column.
+    pub fn set_position_policy(&mut self, policy: PositionPolicy) {
+        self.position_policy = policy;
+    }
+
+    /// Enables or disables the lazy tracking of the positions in the parsers.
+    /// See [ParserInput::set_lazy_tracking()].
+    ///
+    /// **Note**: the lazy positions resolved inside a parser count the lines from the start
+    /// of [StreamingInput::content()], so they restart after discarding the consumed content.
+    pub fn set_lazy_tracking(&mut self, enabled: bool) {
+        self.lazy_tracking = enabled;
+    }
+
+    /// Enables or disables the cursor checks in the parsers. See [ParserInput::set_cursor_checks()].
+    pub fn set_cursor_checks(&mut self, enabled: bool) {
+        self.check_cursors = enabled;
+    }
+
+    /// Sets the rules followed by the parsers to skip the trivia. See [ParserInput::set_trivia()].
+    pub fn set_trivia(&mut self, trivia: Trivia) {
+        self.trivia = trivia;
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Appends `content` to the buffered content.
+    pub fn push_str(&mut self, content: &str) {
+        assert!(
+            !self.finished,
+            "cannot push content to a finished StreamingInput"
+        );
+
+        self.buffer.push_str(content);
+    }
+
+    /// Marks the end of the input so the parsers are not executed in partial mode anymore.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Drops the content that is already parsed to release its memory.
+    /// The current position keeps its char offset, line and column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::StreamingInput;
+    /// # use jpar::characters::read_text;
+    /// let mut stream = StreamingInput::new();
+    /// stream.push_str("first\nsecond");
+    /// stream.parse(|reader| read_text("first\n")(reader).map(|_| ())).unwrap();
+    ///
+    /// stream.discard_consumed();
+    /// assert_eq!(stream.content(), "second");
+    /// assert_eq!(stream.cursor().byte_offset(), 0);
+    /// assert_eq!(stream.cursor().line(), 2);
+    /// ```
+    pub fn discard_consumed(&mut self) {
+        let offset = self.cursor.byte_offset();
+        if offset == 0 {
+            return;
+        }
+
+        self.buffer.drain(..offset);
+        self.cursor = Cursor::new(
+            0,
+            self.cursor.char_offset(),
+            self.cursor.line(),
+            self.cursor.column(),
+        )
+        .with_file_id(self.cursor.file_id());
+    }
+
+    /// Executes `parser` from the current position over the content received so far.
+    ///
+    /// When it succeeds the position is moved to where it has stopped, otherwise
+    /// the position is kept to resume from it in the next call.
+    ///
+    /// The reader follows the settings of the `StreamingInput`, e.g. its [PositionPolicy].
+    pub fn parse<P, R>(&mut self, mut parser: P) -> ParserResult<R, Err>
+    where
+        P: for<'b> FnMut(&mut ParserInput<'b, Err, C>) -> ParserResult<R, Err>,
+    {
+        let context = self.context.take().unwrap();
+        let mut reader = ParserInput::new_with_context_and_error(&self.buffer, context);
+        reader.set_partial(!self.finished);
+        reader.set_position_policy(self.position_policy);
+        reader.set_lazy_tracking(self.lazy_tracking);
+        reader.set_cursor_checks(self.check_cursors);
+        reader.set_trivia(self.trivia);
+        reader.restore(self.cursor.clone());
+
+        let result = parser(&mut reader);
+
+        if result.is_ok() {
+            // Lazy cursors are resolved from the current position to keep the lines
+            // counting from the start of the stream.
+            let cursor = reader.save_cursor();
+            self.cursor = if cursor.is_lazy() {
+                self.buffer.as_str().advance_cursor(
+                    &self.cursor,
+                    cursor.byte_offset(),
+                    &self.position_policy,
+                )
+            } else {
+                cursor
+            };
+        }
+
+        self.context = Some(reader.into_context());
+
+        result
+    }
+}
+
+impl Default for StreamingInput {
+    fn default() -> Self {
+        StreamingInput::new()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::parsers::characters::{ascii_alpha1, read_char, read_text};
+    use crate::parsers::combinator::end;
+    use crate::parsers::sequence::{repeat, terminated, tuple};
+    use crate::parsers::trivia::skip_trivia;
+    use crate::result::{Needed, ParserResultError};
+
+    use super::*;
+
+    fn word(reader: &mut ParserInput) -> ParserResult<String> {
+        let word = terminated(ascii_alpha1, read_char(' '))(reader)?;
+        Ok(word.to_string())
+    }
+
+    #[test]
+    fn test_resume() {
+        let mut stream = StreamingInput::new();
+        assert!(stream.parse(word).unwrap_err().is_incomplete());
+
+        stream.push_str("this is");
+        assert_eq!(stream.parse(word), Ok("this".to_string()));
+        assert_eq!(
+            stream.parse(word),
+            Err(ParserResultError::Incomplete(Needed::Unknown))
+        );
+        assert_eq!(stream.remaining_content(), "is");
+
+        stream.push_str(" a");
+        assert_eq!(stream.parse(word), Ok("is".to_string()));
+        assert!(stream.parse(word).unwrap_err().is_incomplete());
+
+        stream.push_str("\ntest ");
+        assert_eq!(stream.parse(word), Err(ParserResultError::NotFound));
+        assert_eq!(stream.cursor().byte_offset(), 8);
+
+        stream.finish();
+        let result = stream.parse(|reader| {
+            let _ = read_text("a\n")(reader)?;
+            word(reader)
+        });
+        assert_eq!(result, Ok("test".to_string()));
+        assert_eq!(stream.cursor().line(), 2);
+        assert_eq!(stream.parse(end), Ok(()));
+    }
+
+    #[test]
+    fn test_repeat() {
+        let mut stream = StreamingInput::new();
+        let mut parser = |reader: &mut ParserInput| {
+            let (_, count) = tuple((read_char('['), repeat(.., read_text("ab"))))(reader)?;
+            Ok(count.len())
+        };
+
+        stream.push_str("[aba");
+        assert_eq!(
+            stream.parse(&mut parser),
+            Err(ParserResultError::Incomplete(Needed::Size(1)))
+        );
+
+        stream.push_str("bac");
+        assert_eq!(stream.parse(&mut parser), Ok(2));
+        assert_eq!(stream.remaining_content(), "ac");
+    }
+
+    #[test]
+    fn test_context() {
+        let mut stream = StreamingInput::new_with_context(0);
+        let mut parser = |reader: &mut ParserInput<(), usize>| {
+            let result = read_char('a')(reader)?;
+            *reader.context_mut() += 1;
+            Ok(result)
+        };
+
+        stream.push_str("aa");
+        assert_eq!(stream.parse(&mut parser), Ok('a'));
+        assert_eq!(stream.parse(&mut parser), Ok('a'));
+        assert_eq!(
+            stream.parse(&mut parser),
+            Err(ParserResultError::Incomplete(Needed::Size(1)))
+        );
+        assert_eq!(*stream.context(), 2);
+    }
+
+    #[test]
+    fn test_discard_consumed() {
+        let mut stream = StreamingInput::new();
+        stream.push_str("this\nis a");
+        assert_eq!(stream.parse(word), Err(ParserResultError::NotFound));
+        let result = stream.parse(|reader| read_text("this\n")(reader).map(|_| ()));
+        assert_eq!(result, Ok(()));
+
+        stream.discard_consumed();
+        assert_eq!(stream.content(), "is a");
+        assert_eq!(stream.cursor(), &Cursor::new(0, 5, 2, 1));
+
+        assert_eq!(stream.parse(word), Ok("is".to_string()));
+        assert!(stream.parse(word).unwrap_err().is_incomplete());
+
+        stream.discard_consumed();
+        stream.push_str(" test ");
+        assert_eq!(stream.content(), "a test ");
+        assert_eq!(stream.parse(word), Ok("a".to_string()));
+        assert_eq!(stream.cursor(), &Cursor::new(2, 10, 2, 6));
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut stream = StreamingInput::new();
+        stream.set_position_policy(PositionPolicy::new().with_carriage_return(true));
+        stream.set_lazy_tracking(true);
+        stream.set_trivia(Trivia::new().with_line_comments(&["#"]));
+
+        stream.push_str("# comment\ra");
+        let result = stream.parse(|reader| {
+            assert!(reader.is_lazy_tracking());
+            skip_trivia(reader)?;
+            read_char('a')(reader)
+        });
+        assert_eq!(result, Ok('a'));
+        assert!(!stream.cursor().is_lazy());
+        assert_eq!(stream.cursor(), &Cursor::new(11, 11, 2, 2));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::input::Span;
+use crate::ParserInput;
+
+/// A fragment of the content classified by its kind, usually produced by a
+/// [Lexer](crate::lexer::Lexer).
+///
+/// A slice of tokens is also a valid content for a [ParserInput] so that the
+/// combinators can parse them. See [tokens](crate::tokens).
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct Token<'a, K, I: ?Sized = str> {
+    kind: K,
+    span: Span<'a, I>,
+}
+
+impl<'a, K, I: ?Sized> Token<'a, K, I> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `Token` with the specified data.
+    pub fn new(kind: K, span: Span<'a, I>) -> Token<'a, K, I> {
+        Token { kind, span }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The kind of the `Token`.
+    pub fn kind(&self) -> &K {
+        &self.kind
+    }
+
+    /// The `Span` of the `Token` in the content it was read from.
+    pub fn span(&self) -> &Span<'a, I> {
+        &self.span
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Gets the kind and the `Span` of the `Token`.
+    pub fn into_parts(self) -> (K, Span<'a, I>) {
+        (self.kind, self.span)
+    }
+}
+
+impl<'a, K: Clone, I: ?Sized> Clone for Token<'a, K, I> {
+    fn clone(&self) -> Self {
+        Token {
+            kind: self.kind.clone(),
+            span: self.span.clone(),
+        }
+    }
+}
+
+impl<'a, C, Err, K, I: ?Sized> ParserInput<'a, Err, C, [Token<'a, K, I>]> {
+    // METHODS ----------------------------------------------------------------
+
+    /// Consumes the next token if present moving the start index forward.
+    ///
+    /// **Note**: the positions of a token input are measured in tokens.
+    /// Use [Token::span()] to get the position in the original content.
+    pub fn read_token(&mut self) -> Option<&'a Token<'a, K, I>> {
+        match self.peek_token() {
+            Some(v) => {
+                self.consume(1);
+                Some(v)
+            }
+            None => None,
+        }
+    }
+
+    /// Gets the next token if present. This method does not consume the token.
+    pub fn peek_token(&self) -> Option<&'a Token<'a, K, I>> {
+        self.remaining_content().first()
+    }
+}
@@ -0,0 +1,249 @@
+use core::ops::RangeInclusive;
+
+use crate::parsers::characters::UCD_WHITESPACE_CHARS;
+use crate::parsers::verifiers::interval_verifier;
+use crate::result::Needed;
+use crate::{Cursor, ParserInput};
+
+/// The rules used to skip the trivia of a text, i.e. the whitespaces and comments
+/// placed between its tokens. See [ParserInput::skip_trivia()].
+///
+/// By default only the Unicode whitespaces are trivia.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, Trivia};
+/// let mut reader = ParserInput::new("  // line\n /* a /* nested */ block */ # other\nvalue");
+/// reader.set_trivia(
+///     Trivia::new()
+///         .with_line_comments(&["//", "#"])
+///         .with_block_comments(&[("/*", "*/")])
+///         .with_nested_comments(true),
+/// );
+///
+/// reader.skip_trivia().unwrap();
+/// assert!(reader.read_text("value"));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Trivia {
+    whitespace: &'static [RangeInclusive<char>],
+    line_comments: &'static [&'static str],
+    block_comments: &'static [(&'static str, &'static str)],
+    nested_comments: bool,
+}
+
+impl Trivia {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `Trivia` with the default rules.
+    pub const fn new() -> Trivia {
+        Trivia {
+            whitespace: UCD_WHITESPACE_CHARS,
+            line_comments: &[],
+            block_comments: &[],
+            nested_comments: false,
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The intervals of the characters that are whitespaces.
+    pub fn whitespace(&self) -> &'static [RangeInclusive<char>] {
+        self.whitespace
+    }
+
+    /// The markers that start a comment that ends with the line.
+    pub fn line_comments(&self) -> &'static [&'static str] {
+        self.line_comments
+    }
+
+    /// The (start, end) markers of the comments that can span several lines.
+    pub fn block_comments(&self) -> &'static [(&'static str, &'static str)] {
+        self.block_comments
+    }
+
+    /// Whether the block comments can contain other block comments or not.
+    pub fn nested_comments(&self) -> bool {
+        self.nested_comments
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets the intervals of the characters that are whitespaces.
+    /// The intervals must be sorted. See [interval_verifier()].
+    pub fn with_whitespace(mut self, whitespace: &'static [RangeInclusive<char>]) -> Trivia {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Sets the markers that start a comment that ends with the line, e.g. `//` or `#`.
+    pub fn with_line_comments(mut self, markers: &'static [&'static str]) -> Trivia {
+        self.line_comments = markers;
+        self
+    }
+
+    /// Sets the (start, end) markers of the comments that can span several lines, e.g. `/*` and `*/`.
+    pub fn with_block_comments(
+        mut self,
+        markers: &'static [(&'static str, &'static str)],
+    ) -> Trivia {
+        self.block_comments = markers;
+        self
+    }
+
+    /// Sets whether the block comments can contain other block comments or not.
+    pub fn with_nested_comments(mut self, enabled: bool) -> Trivia {
+        self.nested_comments = enabled;
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// The length of the block comment delimited by `start` and `end` at the beginning of `text`.
+    /// Returns `None` if it is not closed.
+    fn block_comment_length(&self, text: &str, start: &str, end: &str) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut offset = 0;
+
+        while offset < text.len() {
+            let remaining = &text[offset..];
+
+            if depth > 0 && remaining.starts_with(end) {
+                depth -= 1;
+                offset += end.len();
+
+                if depth == 0 {
+                    return Some(offset);
+                }
+            } else if remaining.starts_with(start) && (depth == 0 || self.nested_comments) {
+                depth += 1;
+                offset += start.len();
+            } else {
+                offset += remaining.chars().next().unwrap().len_utf8();
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for Trivia {
+    fn default() -> Self {
+        Trivia::new()
+    }
+}
+
+impl<'a, C, Err> ParserInput<'a, Err, C> {
+    // METHODS ----------------------------------------------------------------
+
+    /// Consumes all the trivia at the current position following the rules of the reader,
+    /// returning the consumed text. See [ParserInput::set_trivia()].
+    ///
+    /// Line comments end before the next line break. Block comments that are not closed
+    /// are not trivia, so they are not consumed.
+    ///
+    /// In partial mode, a comment that reaches the end of the content, or the start of
+    /// a comment marker placed at the end of it, returns the content that is needed
+    /// leaving the reader at the initial position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use jpar::{Needed, ParserInput, Trivia};
+    /// let trivia = Trivia::new().with_block_comments(&[("/*", "*/")]);
+    ///
+    /// let mut reader = ParserInput::new(" /* a");
+    /// reader.set_trivia(trivia);
+    /// assert_eq!(reader.skip_trivia(), Ok(" "));
+    ///
+    /// let mut reader = ParserInput::new(" /* a");
+    /// reader.set_trivia(trivia);
+    /// reader.set_partial(true);
+    /// assert_eq!(reader.skip_trivia(), Err(Needed::Unknown));
+    /// assert_eq!(reader.byte_offset(), 0);
+    /// ```
+    pub fn skip_trivia(&mut self) -> Result<&'a str, Needed> {
+        let init_cursor = self.save_cursor();
+        let trivia = *self.trivia();
+
+        loop {
+            let whitespace = self.read_while(interval_verifier(trivia.whitespace));
+
+            match self.skip_comment(&trivia) {
+                Ok(false) if whitespace.is_empty() => break,
+                Ok(_) => {}
+                Err(needed) => {
+                    self.restore(init_cursor);
+                    return Err(needed);
+                }
+            }
+        }
+
+        Ok(self.content_to_current(&init_cursor))
+    }
+
+    /// Consumes a comment at the current position if present.
+    fn skip_comment(&mut self, trivia: &Trivia) -> Result<bool, Needed> {
+        let init_cursor = self.save_cursor();
+
+        if self.is_partial() && !self.is_end() {
+            let remaining = self.remaining_content();
+            let markers = trivia.line_comments.iter();
+            let markers = markers.chain(trivia.block_comments.iter().map(|(start, _)| start));
+
+            for marker in markers {
+                if remaining.len() < marker.len() && marker.starts_with(remaining) {
+                    return Err(Needed::Size(marker.len() - remaining.len()));
+                }
+            }
+        }
+
+        for marker in trivia.line_comments {
+            if self.read_text(marker) {
+                let policy = *self.position_policy();
+                self.read_while(|_, c| !policy.is_line_break(c));
+
+                if self.is_partial() && self.is_end() {
+                    return Err(Needed::Unknown);
+                }
+
+                self.record_comment(&init_cursor);
+                return Ok(true);
+            }
+        }
+
+        for (start, end) in trivia.block_comments {
+            if !self.peek_text(start) {
+                continue;
+            }
+
+            match trivia.block_comment_length(self.remaining_content(), start, end) {
+                Some(length) => {
+                    self.consume(length);
+                    self.record_comment(&init_cursor);
+                    return Ok(true);
+                }
+                None if self.is_partial() => return Err(Needed::Unknown),
+                None => {}
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Records the comment that starts at `start` and ends at the current position
+    /// if the comment collection is enabled.
+    #[allow(unused_variables)]
+    fn record_comment(&mut self, start: &Cursor) {
+        #[cfg(feature = "alloc")]
+        {
+            if !self.collect_comments {
+                return;
+            }
+
+            let comment = self.substring_to_current(start);
+            self.comments.push(comment);
+        }
+    }
+}
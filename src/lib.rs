@@ -1,9 +1,19 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![recursion_limit = "250"]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+pub use diagnostic::*;
+pub use error::*;
 pub use input::*;
 pub use parsers::*;
 pub use result::*;
 
+#[cfg(feature = "alloc")]
+mod diagnostic;
+mod error;
 mod input;
 pub(crate) mod macros;
 mod parsers;
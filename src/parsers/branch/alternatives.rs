@@ -1,31 +1,36 @@
-use std::option::Option::Some;
+use core::option::Option::Some;
 
 use crate::result::{ParserResult, ParserResultError};
 use crate::ParserInput;
 
 /// Helper trait for the [alternative()] combinator.
-pub trait Alternative<'a, C, R, Err> {
+pub trait Alternative<'a, C, R, Err, I: ?Sized = str> {
     /// Tests the specified parser if it exist.
     fn choice(
         &mut self,
         index: usize,
-        reader: &mut ParserInput<'a, Err, C>,
+        reader: &mut ParserInput<'a, Err, C, I>,
     ) -> Option<ParserResult<R, Err>>;
 }
 
 /// Returns the first alternative that matches in order.
-pub fn alternative<'a, P, C, R, Err>(
+///
+/// If the reader is tracking expectations and no alternative matches, the expectations
+/// of every failed alternative are merged at the furthest position reached by them.
+pub fn alternative<'a, P, C, R, Err, I: ?Sized>(
     mut parsers: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: Alternative<'a, C, R, Err>,
+    P: Alternative<'a, C, R, Err, I>,
 {
     move |reader| {
+        let errors_checkpoint = reader.errors_checkpoint();
+
         let mut i = 0;
         while let Some(value) = parsers.choice(i, reader) {
             match value {
                 Ok(v) => return Ok(v),
-                Err(ParserResultError::NotFound) => {}
+                Err(ParserResultError::NotFound) => reader.rollback_errors(errors_checkpoint),
                 Err(e) => return Err(e),
             }
 
@@ -60,14 +65,14 @@ macro_rules! impl_alternative_body (
 macro_rules! impl_alternative_for_tuples (
     // The actual implementation.
     (__impl $($input:ident: $output:ident)+) => {
-        impl<'a, C, R, $($input),+,Err> Alternative<'a, C, R, Err> for ($($input),+,)
+        impl<'a, C, R, $($input),+,Err, I: ?Sized> Alternative<'a, C, R, Err, I> for ($($input),+,)
         where
-            $($input: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>),+
+            $($input: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>),+
         {
             fn choice(
                 &mut self,
                 index: usize,
-                reader: &mut ParserInput<'a, Err, C>,
+                reader: &mut ParserInput<'a, Err, C, I>,
             ) -> Option<ParserResult<R, Err>> {
                 impl_alternative_body!(self, index, reader, $($input)+);
 
@@ -114,10 +119,7 @@ mod test {
     fn test_alternatives_t1() {
         let tuple_size = 1;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -136,10 +138,7 @@ mod test {
     fn test_alternatives_t2() {
         let tuple_size = 2;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -158,10 +157,7 @@ mod test {
     fn test_alternatives_t3() {
         let tuple_size = 3;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -181,10 +177,7 @@ mod test {
     fn test_alternatives_t4() {
         let tuple_size = 4;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -208,10 +201,7 @@ mod test {
     fn test_alternatives_t5() {
         let tuple_size = 5;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -236,10 +226,7 @@ mod test {
     fn test_alternatives_t6() {
         let tuple_size = 6;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -265,10 +252,7 @@ mod test {
     fn test_alternatives_t7() {
         let tuple_size = 7;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -295,10 +279,7 @@ mod test {
     fn test_alternatives_t8() {
         let tuple_size = 8;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -326,10 +307,7 @@ mod test {
     fn test_alternatives_t9() {
         let tuple_size = 9;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -358,10 +336,7 @@ mod test {
     fn test_alternatives_t10() {
         let tuple_size = 10;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
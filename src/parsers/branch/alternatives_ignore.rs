@@ -1,24 +1,24 @@
-use std::option::Option::Some;
+use core::option::Option::Some;
 
 use crate::result::{ParserResult, ParserResultError};
 use crate::ParserInput;
 
 /// Helper trait for the [alternative_ignore()] combinator.
-pub trait AlternativeIgnore<'a, C, Err> {
+pub trait AlternativeIgnore<'a, C, Err, I: ?Sized = str> {
     /// Tests the specified parser if it exist.
     fn choice(
         &mut self,
         index: usize,
-        reader: &mut ParserInput<'a, Err, C>,
+        reader: &mut ParserInput<'a, Err, C, I>,
     ) -> Option<ParserResult<(), Err>>;
 }
 
 /// Returns the first alternative that matches in order.
-pub fn alternative_ignore<'a, P, C, Err>(
+pub fn alternative_ignore<'a, P, C, Err, I: ?Sized>(
     mut parsers: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<(), Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err>
 where
-    P: AlternativeIgnore<'a, C, Err>,
+    P: AlternativeIgnore<'a, C, Err, I>,
 {
     move |reader| {
         let mut i = 0;
@@ -60,14 +60,14 @@ macro_rules! impl_alternative_body (
 macro_rules! impl_alternative_for_tuples (
     // The actual implementation.
     (__impl $($input:ident: $output:ident)+) => {
-        impl<'a, C, $($input),+,$($output),+,Err> AlternativeIgnore<'a, C, Err> for ($($input),+,)
+        impl<'a, C, $($input),+,$($output),+,Err, I: ?Sized> AlternativeIgnore<'a, C, Err, I> for ($($input),+,)
         where
-            $($input: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<$output, Err>),+
+            $($input: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<$output, Err>),+
         {
             fn choice(
                 &mut self,
                 index: usize,
-                reader: &mut ParserInput<'a, Err, C>,
+                reader: &mut ParserInput<'a, Err, C, I>,
             ) -> Option<ParserResult<(), Err>> {
                 impl_alternative_body!(self, index, reader, $($input)+);
 
@@ -114,10 +114,7 @@ mod test {
     fn test_alternatives_t1() {
         let tuple_size = 1;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -137,10 +134,7 @@ mod test {
     fn test_alternatives_t2() {
         let tuple_size = 2;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -160,10 +154,7 @@ mod test {
     fn test_alternatives_t3() {
         let tuple_size = 3;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -185,10 +176,7 @@ mod test {
     fn test_alternatives_t4() {
         let tuple_size = 4;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -213,10 +201,7 @@ mod test {
     fn test_alternatives_t5() {
         let tuple_size = 5;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -242,10 +227,7 @@ mod test {
     fn test_alternatives_t6() {
         let tuple_size = 6;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -272,10 +254,7 @@ mod test {
     fn test_alternatives_t7() {
         let tuple_size = 7;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -303,10 +282,7 @@ mod test {
     fn test_alternatives_t8() {
         let tuple_size = 8;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -335,10 +311,7 @@ mod test {
     fn test_alternatives_t9() {
         let tuple_size = 9;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -368,10 +341,7 @@ mod test {
     fn test_alternatives_t10() {
         let tuple_size = 10;
         for i in 0..=tuple_size {
-            let texts: Vec<_> = (0..=tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..=tuple_size).map(|i| format!("{}", i)).collect();
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
             value[i] = read_text("This");
 
@@ -1,23 +1,26 @@
 pub use alternatives::*;
 pub use alternatives_ignore::*;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 #[cfg(feature = "alloc")]
 use crate::parsers::helpers::map_result;
 use crate::parsers::helpers::not_found_restore;
 use crate::result::{ParserResult, ParserResultError};
-use crate::ParserInput;
+use crate::{InputContent, ParserInput};
 
 mod alternatives;
 mod alternatives_ignore;
 
 /// Executes the `condition` parser and if it match, discards its value and parses `then`.
-pub fn branch_if<'a, Cond, Then, C, R, Rc, Err>(
+pub fn branch_if<'a, Cond, Then, C, R, Rc, Err, I: InputContent + ?Sized>(
     mut condition: Cond,
     mut then: Then,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Option<R>, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Option<R>, Err>
 where
-    Cond: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Rc, Err>,
-    Then: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    Cond: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rc, Err>,
+    Then: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     not_found_restore(move |reader| match condition(reader) {
         Ok(_) => Ok(Some(then(reader)?)),
@@ -27,15 +30,15 @@ where
 }
 
 /// Executes the `condition` parser. If it matches, discards its value and parses `then`, otherwise parses `else`.
-pub fn branch_if_else<'a, Cond, Then, Else, C, R, Rc, Err>(
+pub fn branch_if_else<'a, Cond, Then, Else, C, R, Rc, Err, I: InputContent + ?Sized>(
     mut condition: Cond,
     mut then: Then,
     mut else_parser: Else,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    Cond: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Rc, Err>,
-    Then: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    Else: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    Cond: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rc, Err>,
+    Then: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    Else: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     not_found_restore(move |reader| match condition(reader) {
         Ok(_) => then(reader),
@@ -46,13 +49,13 @@ where
 
 /// Executes the `then` parser while `condition` matches. This method discards `condition` results.
 #[cfg(feature = "alloc")]
-pub fn branch_while<'a, Cond, Then, C, Rc, R, Err>(
+pub fn branch_while<'a, Cond, Then, C, Rc, R, Err, I: InputContent + ?Sized>(
     condition: Cond,
     then: Then,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Vec<R>, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Vec<R>, Err>
 where
-    Cond: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Rc, Err>,
-    Then: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    Cond: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rc, Err>,
+    Then: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     crate::parsers::sequence::repeat(
         ..,
@@ -0,0 +1,207 @@
+use core::convert::TryInto;
+use core::mem::size_of;
+
+use crate::error::Expected;
+use crate::parsers::helpers::{and_then, map_result};
+use crate::result::{Needed, ParserResult, ParserResultError};
+use crate::ParserInput;
+
+macro_rules! impl_number_parser {
+    ($type:ty, $be_name:ident, $le_name:ident $(,)?) => {
+        #[doc = concat!("Reads a big-endian `", stringify!($type), "`.")]
+        pub fn $be_name<'a, C, Err>(
+            reader: &mut ParserInput<'a, Err, C, [u8]>,
+        ) -> ParserResult<$type, Err> {
+            map_result(read_bytes(size_of::<$type>()), |_, v| {
+                <$type>::from_be_bytes(v.try_into().unwrap())
+            })(reader)
+        }
+
+        #[doc = concat!("Reads a little-endian `", stringify!($type), "`.")]
+        pub fn $le_name<'a, C, Err>(
+            reader: &mut ParserInput<'a, Err, C, [u8]>,
+        ) -> ParserResult<$type, Err> {
+            map_result(read_bytes(size_of::<$type>()), |_, v| {
+                <$type>::from_le_bytes(v.try_into().unwrap())
+            })(reader)
+        }
+    };
+}
+
+impl_number_parser!(u16, be_u16, le_u16);
+impl_number_parser!(u32, be_u32, le_u32);
+impl_number_parser!(u64, be_u64, le_u64);
+impl_number_parser!(f32, be_f32, le_f32);
+impl_number_parser!(f64, be_f64, le_f64);
+
+/// Reads a byte.
+pub fn read_byte<C, Err>(reader: &mut ParserInput<Err, C, [u8]>) -> ParserResult<u8, Err> {
+    match reader.read_byte() {
+        Some(v) => Ok(v),
+        None if reader.is_partial() => Err(ParserResultError::Incomplete(Needed::Size(1))),
+        None => Err(ParserResultError::NotFound),
+    }
+}
+
+/// Reads `count` bytes.
+pub fn read_bytes<'a, C, Err>(
+    count: usize,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, [u8]>) -> ParserResult<&'a [u8], Err> {
+    move |reader| match reader.read_bytes(count) {
+        Some(v) => Ok(v),
+        None if reader.is_partial() => Err(ParserResultError::Incomplete(Needed::Size(
+            count - reader.remaining_length(),
+        ))),
+        None => Err(ParserResultError::NotFound),
+    }
+}
+
+/// Reads a specific sequence of bytes, e.g. a magic number.
+pub fn tag<'a, C, Err>(
+    tag: &'a [u8],
+) -> impl FnMut(&mut ParserInput<'a, Err, C, [u8]>) -> ParserResult<&'a [u8], Err> {
+    move |reader| {
+        if reader.read_tag(tag) {
+            Ok(tag)
+        } else {
+            let remaining = reader.remaining_content();
+            if reader.is_partial() && tag.starts_with(remaining) {
+                return Err(ParserResultError::Incomplete(Needed::Size(
+                    tag.len() - remaining.len(),
+                )));
+            }
+
+            reader.expect(Expected::Bytes(tag));
+            Err(ParserResultError::NotFound)
+        }
+    }
+}
+
+/// Interprets the bytes read by `parser` as UTF-8 text.
+/// It is not found if they are not valid UTF-8.
+///
+/// Combined with [map_parser()](crate::helpers::map_parser) it allows to parse a fragment of a binary input as text.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::ParserInput;
+/// # use jpar::bytes::{be_u16, read_bytes, utf8};
+/// # use jpar::characters::ascii_alpha1;
+/// # use jpar::helpers::map_parser;
+/// let mut reader = ParserInput::new_bytes(b"\x00\x04jpar");
+///
+/// let length = be_u16(&mut reader).unwrap();
+/// let result = map_parser(utf8(read_bytes(length as usize)), ascii_alpha1)(&mut reader);
+/// assert_eq!(result, Ok("jpar"));
+/// ```
+pub fn utf8<'a, P, C, Err>(
+    parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, [u8]>) -> ParserResult<&'a str, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, [u8]>) -> ParserResult<&'a [u8], Err>,
+{
+    and_then(parser, |_, bytes| {
+        core::str::from_utf8(bytes).map_err(|_| ParserResultError::NotFound)
+    })
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::parsers::characters::{ascii_alpha1, read_char};
+    use crate::parsers::helpers::map_parser;
+    use crate::parsers::sequence::tuple;
+
+    use super::*;
+
+    fn bytes<'a>(content: &'a [u8]) -> ParserInput<'a, (), (), [u8]> {
+        ParserInput::new_bytes(content)
+    }
+
+    #[test]
+    fn test_read_byte() {
+        let mut reader = bytes(b"\x01");
+
+        assert_eq!(read_byte(&mut reader), Ok(1));
+        assert_eq!(read_byte(&mut reader), Err(ParserResultError::NotFound));
+
+        reader.set_partial(true);
+        assert_eq!(
+            read_byte(&mut reader),
+            Err(ParserResultError::Incomplete(Needed::Size(1)))
+        );
+    }
+
+    #[test]
+    fn test_read_bytes() {
+        let mut reader = bytes(b"\x01\x02\x03");
+
+        assert_eq!(read_bytes(2)(&mut reader), Ok(&b"\x01\x02"[..]));
+        assert_eq!(read_bytes(2)(&mut reader), Err(ParserResultError::NotFound));
+
+        reader.set_partial(true);
+        assert_eq!(
+            read_bytes(4)(&mut reader),
+            Err(ParserResultError::Incomplete(Needed::Size(3)))
+        );
+        assert_eq!(read_bytes(1)(&mut reader), Ok(&b"\x03"[..]));
+    }
+
+    #[test]
+    fn test_tag() {
+        let mut reader = bytes(b"\x89PNG\r\n");
+
+        assert_eq!(tag(b"\x89PNG")(&mut reader), Ok(&b"\x89PNG"[..]));
+        assert_eq!(tag(b"\n")(&mut reader), Err(ParserResultError::NotFound));
+
+        reader.set_partial(true);
+        assert_eq!(
+            tag(b"\r\n\x1a\n")(&mut reader),
+            Err(ParserResultError::Incomplete(Needed::Size(2)))
+        );
+        assert_eq!(reader.byte_offset(), 4);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let mut reader = bytes(&[
+            0x01, 0x02, 0x01, 0x02, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x3f, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0xf0, 0x3f,
+        ]);
+
+        assert_eq!(be_u16(&mut reader), Ok(0x0102));
+        assert_eq!(le_u16(&mut reader), Ok(0x0201));
+        assert_eq!(be_u32(&mut reader), Ok(1));
+        assert_eq!(le_u32(&mut reader), Ok(1));
+        assert_eq!(be_u64(&mut reader), Ok(1));
+        assert_eq!(be_f32(&mut reader), Ok(1.0));
+        assert_eq!(le_f64(&mut reader), Ok(1.0));
+        assert_eq!(le_u64(&mut reader), Err(ParserResultError::NotFound));
+    }
+
+    #[test]
+    fn test_utf8() {
+        let parser = |reader: &mut ParserInput<'static, (), (), [u8]>| {
+            let _ = tag(b"JP")(reader)?;
+            let length = be_u16(reader)?;
+
+            map_parser(
+                utf8(read_bytes(length as usize)),
+                tuple((ascii_alpha1, read_char('!'))),
+            )(reader)
+        };
+
+        let mut reader = bytes(b"JP\x00\x05test!\x00");
+        assert_eq!(parser(&mut reader), Ok(("test", '!')));
+        assert_eq!(reader.remaining_content(), b"\x00");
+
+        let mut reader = bytes(b"JP\x00\x05te\xffs!");
+        assert_eq!(parser(&mut reader), Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 4);
+    }
+}
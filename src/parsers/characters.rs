@@ -1,8 +1,9 @@
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
-use crate::parsers::helpers::map_result;
+use crate::error::Expected;
+use crate::parsers::helpers::{expecting, map_result};
 use crate::parsers::Quantifier;
-use crate::result::{ParserResult, ParserResultError};
+use crate::result::{Needed, ParserResult, ParserResultError};
 use crate::ParserInput;
 
 pub static ASCII_ALPHA_CHARS: &[RangeInclusive<char>] = &['A'..='Z', 'a'..='z'];
@@ -50,24 +51,42 @@ macro_rules! impl_range_parser {
     ($chars:expr, $name:ident, $comment:literal, $name0:ident, $comment0:literal, $name1:ident, $comment1:literal, $name_qtf:ident, $comment_qtf:literal $(,)?) => {
         #[doc = $comment]
         pub fn $name<'a, C, Err>(reader: &mut ParserInput<'a, Err, C>) -> ParserResult<char, Err> {
-            read_any_of(crate::parsers::verifiers::interval_verifier($chars))(reader)
+            expecting(
+                Expected::Interval($chars),
+                read_any_of(crate::parsers::verifiers::interval_verifier($chars)),
+            )(reader)
         }
 
         #[doc = $comment0]
-        pub fn $name0<'a, C, Err>(reader: &mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err> {
-            read_any_of_quantified(0.., crate::parsers::verifiers::interval_verifier($chars))(reader)
+        pub fn $name0<'a, C, Err>(
+            reader: &mut ParserInput<'a, Err, C>,
+        ) -> ParserResult<&'a str, Err> {
+            read_any_of_quantified(0.., crate::parsers::verifiers::interval_verifier($chars))(
+                reader,
+            )
         }
 
         #[doc = $comment1]
-        pub fn $name1<'a, C, Err>(reader: &mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err> {
-            read_any_of_quantified(1.., crate::parsers::verifiers::interval_verifier($chars))(reader)
+        pub fn $name1<'a, C, Err>(
+            reader: &mut ParserInput<'a, Err, C>,
+        ) -> ParserResult<&'a str, Err> {
+            expecting(
+                Expected::Interval($chars),
+                read_any_of_quantified(1.., crate::parsers::verifiers::interval_verifier($chars)),
+            )(reader)
         }
 
         #[doc = $comment_qtf]
         pub fn $name_qtf<'a, C, Err>(
             quantifier: impl Into<Quantifier>,
         ) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err> {
-            read_any_of_quantified(quantifier, crate::parsers::verifiers::interval_verifier($chars))
+            expecting(
+                Expected::Interval($chars),
+                read_any_of_quantified(
+                    quantifier,
+                    crate::parsers::verifiers::interval_verifier($chars),
+                ),
+            )
         }
     };
 }
@@ -185,14 +204,14 @@ pub fn read_char<'a, C, Err>(
     character: char,
 ) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<char, Err> {
     move |reader| match reader.peek() {
-        Some(v) => {
-            if v == character {
-                Ok(reader.read().unwrap())
-            } else {
-                Err(ParserResultError::NotFound)
-            }
+        Some(v) if v == character => Ok(reader.read().unwrap()),
+        None if reader.is_partial() => Err(ParserResultError::Incomplete(Needed::Size(
+            character.len_utf8(),
+        ))),
+        _ => {
+            reader.expect(Expected::Char(character));
+            Err(ParserResultError::NotFound)
         }
-        None => Err(ParserResultError::NotFound),
     }
 }
 
@@ -201,17 +220,20 @@ pub fn read_char_no_case<'a, C, Err>(
     character: char,
 ) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<char, Err> {
     move |reader| match reader.peek() {
-        Some(v) => {
+        Some(v)
             if character
                 .to_lowercase()
-                .any(|lower_c| lower_c == v.to_lowercase().next().unwrap())
-            {
-                Ok(reader.read().unwrap())
-            } else {
-                Err(ParserResultError::NotFound)
-            }
+                .any(|lower_c| lower_c == v.to_lowercase().next().unwrap()) =>
+        {
+            Ok(reader.read().unwrap())
+        }
+        None if reader.is_partial() => Err(ParserResultError::Incomplete(Needed::Size(
+            character.len_utf8(),
+        ))),
+        _ => {
+            reader.expect(Expected::Char(character));
+            Err(ParserResultError::NotFound)
         }
-        None => Err(ParserResultError::NotFound),
     }
 }
 
@@ -223,6 +245,14 @@ pub fn read_text<'a, C, Err>(
         if reader.read_text(text) {
             Ok(text)
         } else {
+            let remaining = reader.remaining_content();
+            if reader.is_partial() && text.starts_with(remaining) {
+                return Err(ParserResultError::Incomplete(Needed::Size(
+                    text.len() - remaining.len(),
+                )));
+            }
+
+            reader.expect(Expected::Text(text));
             Err(ParserResultError::NotFound)
         }
     }
@@ -233,6 +263,7 @@ pub fn read_text_no_case<'a, C, Err>(
     text: &'a str,
 ) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err> {
     move |reader| {
+        let init_cursor = reader.save_cursor();
         let mut chars = text.chars();
         let result = reader.read_while(|i, c| {
             i < text.len()
@@ -246,6 +277,14 @@ pub fn read_text_no_case<'a, C, Err>(
         if result.len() == text.len() {
             Ok(result)
         } else {
+            let is_incomplete = reader.is_partial() && reader.is_end();
+            reader.restore(init_cursor);
+
+            if is_incomplete {
+                return Err(ParserResultError::Incomplete(Needed::Unknown));
+            }
+
+            reader.expect(Expected::Text(text));
             Err(ParserResultError::NotFound)
         }
     }
@@ -256,9 +295,15 @@ pub fn read_any_quantified<'a, C, Err>(
     quantifier: impl Into<Quantifier>,
 ) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err> {
     let quantifier = quantifier.into();
-    move |reader| match reader.read_quantified(quantifier) {
-        Some(v) => Ok(v),
-        None => Err(ParserResultError::NotFound),
+    move |reader| {
+        if needs_more_content(reader, &quantifier, |_, _| true) {
+            return Err(ParserResultError::Incomplete(Needed::Unknown));
+        }
+
+        match reader.read_quantified(quantifier) {
+            Some(v) => Ok(v),
+            None => Err(ParserResultError::NotFound),
+        }
     }
 }
 
@@ -283,9 +328,15 @@ pub fn read_any_of_quantified<'a, C, Err>(
     verifier: impl Fn(usize, char) -> bool,
 ) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err> {
     let quantifier = quantifier.into();
-    move |reader| match reader.read_while_quantified(quantifier, |i, c| verifier(i, c)) {
-        Some(v) => Ok(v),
-        None => Err(ParserResultError::NotFound),
+    move |reader| {
+        if needs_more_content(reader, &quantifier, &verifier) {
+            return Err(ParserResultError::Incomplete(Needed::Unknown));
+        }
+
+        match reader.read_while_quantified(quantifier, &verifier) {
+            Some(v) => Ok(v),
+            None => Err(ParserResultError::NotFound),
+        }
     }
 }
 
@@ -318,9 +369,15 @@ pub fn read_none_of_quantified<'a, C, Err>(
     verifier: impl Fn(usize, char) -> bool,
 ) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err> {
     let quantifier = quantifier.into();
-    move |reader| match reader.read_while_quantified(quantifier, |i, c| !verifier(i, c)) {
-        Some(v) => Ok(v),
-        None => Err(ParserResultError::NotFound),
+    move |reader| {
+        if needs_more_content(reader, &quantifier, |i, c| !verifier(i, c)) {
+            return Err(ParserResultError::Incomplete(Needed::Unknown));
+        }
+
+        match reader.read_while_quantified(quantifier, |i, c| !verifier(i, c)) {
+            Some(v) => Ok(v),
+            None => Err(ParserResultError::NotFound),
+        }
     }
 }
 
@@ -347,6 +404,27 @@ pub fn read_none_of1<'a, C, Err>(
     read_none_of_quantified(1.., verifier)
 }
 
+/// Whether a partial reader reaches the end of its content while reading the characters
+/// validated by `verifier` before `quantifier` is finished, i.e. more content could change the result.
+fn needs_more_content<C, Err>(
+    reader: &ParserInput<Err, C>,
+    quantifier: &Quantifier,
+    mut verifier: impl FnMut(usize, char) -> bool,
+) -> bool {
+    if !reader.is_partial() {
+        return false;
+    }
+
+    let mut count = 0;
+    let result = reader.peek_while(|i, c| {
+        let valid = !quantifier.is_finished(i) && verifier(i, c);
+        count += valid as usize;
+        valid
+    });
+
+    result.len() == reader.remaining_length() && !quantifier.is_finished(count)
+}
+
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
@@ -454,4 +532,38 @@ mod test {
         let result = read_none_of_quantified(1.., interval_verifier(&[' '..=' ']))(&mut reader);
         assert_eq!(result, Err(ParserResultError::NotFound));
     }
+
+    #[test]
+    fn test_partial() {
+        let mut reader = ParserInput::new("This is");
+        reader.set_partial(true);
+
+        let result = read_text("This ")(&mut reader);
+        assert_eq!(result, Ok("This "));
+
+        let result = read_text("island")(&mut reader);
+        assert_eq!(result, Err(ParserResultError::Incomplete(Needed::Size(4))));
+
+        let result = read_text("it")(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+
+        let result = read_any_of_quantified(..=2, interval_verifier(&['a'..='z']))(&mut reader);
+        assert_eq!(result, Ok("is"));
+
+        let result = read_char('a')(&mut reader);
+        assert_eq!(result, Err(ParserResultError::Incomplete(Needed::Size(1))));
+
+        let mut reader = ParserInput::new("abc");
+        reader.set_partial(true);
+
+        let result = read_any_of1(interval_verifier(&['a'..='z']))(&mut reader);
+        assert_eq!(result, Err(ParserResultError::Incomplete(Needed::Unknown)));
+        assert_eq!(reader.byte_offset(), 0);
+
+        let result = read_any_quantified(2)(&mut reader);
+        assert_eq!(result, Ok("ab"));
+
+        let result = read_none_of1(interval_verifier(&['a'..='z']))(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+    }
 }
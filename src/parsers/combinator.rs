@@ -1,22 +1,31 @@
+use crate::error::Expected;
 use crate::parsers::helpers::not_found_restore;
-use crate::result::{ParserResult, ParserResultError};
-use crate::ParserInput;
+use crate::result::{Needed, ParserResult, ParserResultError};
+use crate::{InputContent, ParserInput};
 
 /// It is ok only at the end of the input.
-pub fn end<C, Err>(reader: &mut ParserInput<Err, C>) -> ParserResult<(), Err> {
+/// In partial mode the end of the content is not the end of the input so it is incomplete.
+pub fn end<C, Err, I: InputContent + ?Sized>(
+    reader: &mut ParserInput<Err, C, I>,
+) -> ParserResult<(), Err> {
     if reader.is_end() {
+        if reader.is_partial() {
+            return Err(ParserResultError::Incomplete(Needed::Unknown));
+        }
+
         Ok(())
     } else {
+        reader.expect(Expected::End);
         Err(ParserResultError::NotFound)
     }
 }
 
 /// Executes the parser and returns its value not consuming any character in the process.
-pub fn not_consume<'a, P, C, R, Err>(
+pub fn not_consume<'a, P, C, R, Err, I: InputContent + ?Sized>(
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     move |reader| {
         let init_cursor = reader.save_cursor();
@@ -28,13 +37,13 @@ where
 }
 
 /// Returns the result of the child parser if it satisfies a verification function.
-pub fn verify<'a, C, R, P, V, Err>(
+pub fn verify<'a, C, R, P, V, Err, I: InputContent + ?Sized>(
     mut parser: P,
     mut verifier: V,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    V: FnMut(&mut ParserInput<'a, Err, C>, &R) -> bool,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    V: FnMut(&mut ParserInput<'a, Err, C, I>, &R) -> bool,
 {
     not_found_restore(move |reader| {
         let result = parser(reader)?;
@@ -48,11 +57,11 @@ where
 }
 
 /// Returns None when the the parser is not found.
-pub fn optional<'a, P, C, R, Err>(
+pub fn optional<'a, P, C, R, Err, I: ?Sized>(
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Option<R>, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Option<R>, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     move |reader| match parser(reader) {
         Ok(v) => Ok(Some(v)),
@@ -62,11 +71,11 @@ where
 }
 
 /// Returns the default value of `R` when the the parser is not found.
-pub fn optional_default<'a, P, C, R: Default, Err>(
+pub fn optional_default<'a, P, C, R: Default, Err, I: ?Sized>(
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     move |reader| match parser(reader) {
         Ok(v) => Ok(v),
@@ -76,11 +85,11 @@ where
 }
 
 /// Returns the default value of `R` when the the parser is not found.
-pub fn not<'a, P, C, R, Err>(
+pub fn not<'a, P, C, R, Err, I: ?Sized>(
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<(), Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     move |reader| match parser(reader) {
         Ok(_) => Err(ParserResultError::NotFound),
@@ -90,16 +99,20 @@ where
 }
 
 /// Succeeds if all the input has been consumed by its child parser.
-pub fn all_consumed<'a, P, C, R, Err>(
+pub fn all_consumed<'a, P, C, R, Err, I: InputContent + ?Sized>(
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     move |reader| {
         let result = parser(reader)?;
 
         if reader.is_end() {
+            if reader.is_partial() {
+                return Err(ParserResultError::Incomplete(Needed::Unknown));
+            }
+
             Ok(result)
         } else {
             Err(ParserResultError::NotFound)
@@ -126,6 +139,10 @@ mod test {
         let mut reader = ParserInput::new("");
         let result = end(&mut reader);
         assert_eq!(result, Ok(()));
+
+        reader.set_partial(true);
+        let result = end(&mut reader);
+        assert_eq!(result, Err(ParserResultError::Incomplete(Needed::Unknown)));
     }
 
     #[test]
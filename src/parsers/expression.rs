@@ -0,0 +1,436 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::input::InputContent;
+use crate::result::{ParserResult, ParserResultError};
+use crate::{ParserInput, Span};
+
+type BoxedParser<'a, 'p, R, Err, C, I> =
+    Box<dyn FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> + 'p>;
+type PrefixFold<'a, 'p, R, Err, I> = Box<dyn FnMut(&Span<'a, I>, R) -> Result<R, Err> + 'p>;
+type InfixFold<'a, 'p, R, Err, I> = Box<dyn FnMut(R, &Span<'a, I>, R) -> Result<R, Err> + 'p>;
+type PostfixFold<'a, 'p, R, Err, I> = Box<dyn FnMut(R, &Span<'a, I>) -> Result<R, Err> + 'p>;
+
+/// The associativity of an infix operator, i.e. how a sequence of operators
+/// with the same precedence are grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    /// `a - b - c` is grouped as `(a - b) - c`.
+    Left,
+    /// `a ^ b ^ c` is grouped as `a ^ (b ^ c)`.
+    Right,
+}
+
+struct PrefixOperator<'a, 'p, R, Err, C, I: ?Sized> {
+    precedence: u32,
+    parser: BoxedParser<'a, 'p, (), Err, C, I>,
+    fold: PrefixFold<'a, 'p, R, Err, I>,
+}
+
+struct InfixOperator<'a, 'p, R, Err, C, I: ?Sized> {
+    precedence: u32,
+    associativity: Associativity,
+    parser: BoxedParser<'a, 'p, (), Err, C, I>,
+    fold: InfixFold<'a, 'p, R, Err, I>,
+}
+
+struct PostfixOperator<'a, 'p, R, Err, C, I: ?Sized> {
+    precedence: u32,
+    parser: BoxedParser<'a, 'p, (), Err, C, I>,
+    fold: PostfixFold<'a, 'p, R, Err, I>,
+}
+
+/// A builder of expression parsers that uses precedence climbing (Pratt parsing)
+/// to combine the results of an atom parser through prefix, infix and postfix operators.
+///
+/// The precedences are relative: the higher the precedence the tighter the operator binds.
+/// Operators of the same kind are tested in the order they were registered, so an
+/// operator must be registered before any other that is a prefix of it, e.g. `**` before `*`.
+///
+/// The fold callbacks receive the `Span` of the operator so that their errors can refer to it.
+/// When they fail, their error is placed at the start of the operator.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::ParserInput;
+/// # use jpar::characters::read_char;
+/// # use jpar::expression::{Associativity, ExpressionBuilder};
+/// # use jpar::numbers::unsigned;
+/// let mut parser = ExpressionBuilder::new(unsigned::<i64, _, _>())
+///     .prefix(3, read_char('-'), |_, v| Ok(-v))
+///     .infix(1, Associativity::Left, read_char('-'), |l, _, r| Ok(l - r))
+///     .infix(2, Associativity::Left, read_char('*'), |l, _, r| Ok(l * r))
+///     .infix(4, Associativity::Right, read_char('^'), |l, _, r: i64| Ok(l.pow(r as u32)))
+///     .build();
+///
+/// let mut reader = ParserInput::new("-2*3^2^1-1");
+/// assert_eq!(parser(&mut reader), Ok(-19));
+/// ```
+pub struct ExpressionBuilder<'a, 'p, R, Err = (), C = (), I: ?Sized = str> {
+    atom: BoxedParser<'a, 'p, R, Err, C, I>,
+    prefix: Vec<PrefixOperator<'a, 'p, R, Err, C, I>>,
+    infix: Vec<InfixOperator<'a, 'p, R, Err, C, I>>,
+    postfix: Vec<PostfixOperator<'a, 'p, R, Err, C, I>>,
+}
+
+impl<'a, 'p, R, Err, C, I: InputContent + ?Sized> ExpressionBuilder<'a, 'p, R, Err, C, I> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `ExpressionBuilder` whose operands are read by `atom`.
+    pub fn new<P>(atom: P) -> ExpressionBuilder<'a, 'p, R, Err, C, I>
+    where
+        P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> + 'p,
+    {
+        ExpressionBuilder {
+            atom: Box::new(atom),
+            prefix: Vec::new(),
+            infix: Vec::new(),
+            postfix: Vec::new(),
+        }
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Registers a prefix operator read by `parser` whose operand is folded by `fold`.
+    /// The operand includes every infix or postfix operator with a precedence
+    /// greater or equal than `precedence`.
+    pub fn prefix<P, Rp, F>(mut self, precedence: u32, mut parser: P, fold: F) -> Self
+    where
+        P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rp, Err> + 'p,
+        F: FnMut(&Span<'a, I>, R) -> Result<R, Err> + 'p,
+    {
+        self.prefix.push(PrefixOperator {
+            precedence,
+            parser: Box::new(move |reader| parser(reader).map(|_| ())),
+            fold: Box::new(fold),
+        });
+        self
+    }
+
+    /// Registers an infix operator read by `parser` whose operands are folded by `fold`.
+    pub fn infix<P, Rp, F>(
+        mut self,
+        precedence: u32,
+        associativity: Associativity,
+        mut parser: P,
+        fold: F,
+    ) -> Self
+    where
+        P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rp, Err> + 'p,
+        F: FnMut(R, &Span<'a, I>, R) -> Result<R, Err> + 'p,
+    {
+        self.infix.push(InfixOperator {
+            precedence,
+            associativity,
+            parser: Box::new(move |reader| parser(reader).map(|_| ())),
+            fold: Box::new(fold),
+        });
+        self
+    }
+
+    /// Registers a postfix operator read by `parser` whose operand is folded by `fold`.
+    pub fn postfix<P, Rp, F>(mut self, precedence: u32, mut parser: P, fold: F) -> Self
+    where
+        P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rp, Err> + 'p,
+        F: FnMut(R, &Span<'a, I>) -> Result<R, Err> + 'p,
+    {
+        self.postfix.push(PostfixOperator {
+            precedence,
+            parser: Box::new(move |reader| parser(reader).map(|_| ())),
+            fold: Box::new(fold),
+        });
+        self
+    }
+
+    /// Builds the expression parser.
+    pub fn build(
+        mut self,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> + 'p
+    where
+        'a: 'p,
+        R: 'p,
+        Err: 'p,
+        C: 'p,
+        I: 'p,
+    {
+        move |reader| {
+            let init_cursor = reader.save_cursor();
+            let result = self.parse_expression(reader, 0);
+
+            if let Err(ParserResultError::NotFound) = result {
+                reader.restore(init_cursor);
+            }
+
+            result
+        }
+    }
+
+    /// Reads an expression whose operators have a precedence greater or equal than `min_precedence`.
+    fn parse_expression(
+        &mut self,
+        reader: &mut ParserInput<'a, Err, C, I>,
+        min_precedence: u32,
+    ) -> ParserResult<R, Err> {
+        let mut left = self.parse_prefix(reader)?;
+
+        'operators: loop {
+            for index in 0..self.postfix.len() {
+                let operator = &mut self.postfix[index];
+                if operator.precedence < min_precedence {
+                    continue;
+                }
+
+                if let Some(span) = read_operator(reader, &mut operator.parser)? {
+                    left = fold_result(&span, (operator.fold)(left, &span))?;
+                    continue 'operators;
+                }
+            }
+
+            for index in 0..self.infix.len() {
+                let operator = &mut self.infix[index];
+                if operator.precedence < min_precedence {
+                    continue;
+                }
+
+                let span = match read_operator(reader, &mut operator.parser)? {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                // At the maximum precedence a left associative operator cannot bind
+                // any other operator to its right operand.
+                let next_precedence = match operator.associativity {
+                    Associativity::Left => operator.precedence.checked_add(1),
+                    Associativity::Right => Some(operator.precedence),
+                };
+
+                let right = match next_precedence {
+                    Some(v) => self.parse_expression(reader, v),
+                    None => self.parse_prefix(reader),
+                };
+
+                let right = match right {
+                    Ok(v) => v,
+                    Err(ParserResultError::NotFound) => {
+                        // Without right operand the operator does not belong to the expression.
+                        reader.restore(span.start_cursor().clone());
+                        break 'operators;
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                let operator = &mut self.infix[index];
+                left = fold_result(&span, (operator.fold)(left, &span, right))?;
+                continue 'operators;
+            }
+
+            break;
+        }
+
+        Ok(left)
+    }
+
+    /// Reads an atom preceded by any number of prefix operators.
+    fn parse_prefix(&mut self, reader: &mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> {
+        for index in 0..self.prefix.len() {
+            let operator = &mut self.prefix[index];
+            let span = match read_operator(reader, &mut operator.parser)? {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let precedence = operator.precedence;
+            let operand = match self.parse_expression(reader, precedence) {
+                Ok(v) => v,
+                Err(ParserResultError::NotFound) => {
+                    reader.restore(span.start_cursor().clone());
+                    return Err(ParserResultError::NotFound);
+                }
+                Err(e) => return Err(e),
+            };
+
+            let operator = &mut self.prefix[index];
+            return fold_result(&span, (operator.fold)(&span, operand));
+        }
+
+        (self.atom)(reader)
+    }
+}
+
+/// Reads an operator returning its `Span` if found.
+fn read_operator<'a, Err, C, I: InputContent + ?Sized>(
+    reader: &mut ParserInput<'a, Err, C, I>,
+    parser: &mut BoxedParser<'a, '_, (), Err, C, I>,
+) -> ParserResult<Option<Span<'a, I>>, Err> {
+    let init_cursor = reader.save_cursor();
+
+    match parser(reader) {
+        Ok(()) => Ok(Some(reader.substring_to_current(&init_cursor))),
+        Err(ParserResultError::NotFound) => {
+            reader.restore(init_cursor);
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Places the error of a fold callback at the start of the operator.
+fn fold_result<R, Err, I: InputContent + ?Sized>(
+    span: &Span<I>,
+    result: Result<R, Err>,
+) -> ParserResult<R, Err> {
+    result.map_err(|e| ParserResultError::Error((span.start_cursor().clone(), e)))
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::parsers::characters::{decimal_digit1, read_char, read_text};
+    use crate::parsers::helpers::map_result;
+    use crate::parsers::sequence::delimited;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct FoldError {
+        message: &'static str,
+        operator: (usize, &'static str),
+    }
+
+    impl FoldError {
+        fn new(message: &'static str, operator: &Span<'static>) -> FoldError {
+            FoldError {
+                message,
+                operator: (operator.start_cursor().byte_offset(), operator.content()),
+            }
+        }
+    }
+
+    fn number<Err>(reader: &mut ParserInput<'static, Err>) -> ParserResult<i64, Err> {
+        map_result(decimal_digit1, |_, v| v.parse().unwrap())(reader)
+    }
+
+    fn expression(reader: &mut ParserInput<'static, FoldError>) -> ParserResult<i64, FoldError> {
+        ExpressionBuilder::new(|reader: &mut ParserInput<'static, FoldError>| {
+            match number(reader) {
+                Err(ParserResultError::NotFound) => {
+                    delimited(read_char('('), expression, read_char(')'))(reader)
+                }
+                other => other,
+            }
+        })
+        .prefix(4, read_char('-'), |_, v| Ok(-v))
+        .infix(1, Associativity::Left, read_char('+'), |l, _, r| Ok(l + r))
+        .infix(1, Associativity::Left, read_char('-'), |l, _, r| Ok(l - r))
+        .infix(3, Associativity::Right, read_text("**"), |l, _, r| {
+            Ok(l.pow(r as u32))
+        })
+        .infix(2, Associativity::Left, read_char('*'), |l, _, r| Ok(l * r))
+        .infix(2, Associativity::Left, read_char('/'), |l, span, r| {
+            if r == 0 {
+                Err(FoldError::new("division by zero", span))
+            } else {
+                Ok(l / r)
+            }
+        })
+        .postfix(5, read_char('!'), |v, _| Ok((1..=v).product()))
+        .build()(reader)
+    }
+
+    #[test]
+    fn test_precedence() {
+        let tests = [
+            ("1+2*3", 7),
+            ("(1+2)*3", 9),
+            ("10-4-3", 3),
+            ("2**3**2", 512),
+            ("-2**2", 4),
+            ("2*-3", -6),
+            ("3!*2", 12),
+            ("-3!", -6),
+            ("1+2**2*3!-4/2", 23),
+        ];
+
+        for (text, value) in tests.iter() {
+            let mut reader = ParserInput::new_with_error(text);
+            assert_eq!(expression(&mut reader), Ok(*value), "Input: {}", text);
+            assert!(reader.is_end(), "Input: {}", text);
+        }
+    }
+
+    #[test]
+    fn test_missing_operand() {
+        let mut reader = ParserInput::new_with_error("1+2*");
+        assert_eq!(expression(&mut reader), Ok(3));
+        assert_eq!(reader.remaining_content(), "*");
+
+        let mut reader = ParserInput::new_with_error("-+1");
+        assert_eq!(expression(&mut reader), Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 0);
+    }
+
+    #[test]
+    fn test_fold_error() {
+        let mut reader = ParserInput::new_with_error("1 + 2//0");
+        let mut parser = ExpressionBuilder::new(number)
+            .infix(
+                1,
+                Associativity::Left,
+                read_text(" + "),
+                |l, _, r| Ok(l + r),
+            )
+            .infix(2, Associativity::Left, read_text("//"), |_, span, _| {
+                Err(FoldError::new("division by zero", span))
+            })
+            .build();
+
+        let (cursor, error) = parser(&mut reader).unwrap_err().unwrap_error();
+        assert_eq!(cursor.byte_offset(), 5);
+        assert_eq!(error.message, "division by zero");
+        assert_eq!(error.operator, (5, "//"));
+
+        let mut reader = ParserInput::new_with_error("4/(3-3)");
+        let (cursor, error) = expression(&mut reader).unwrap_err().unwrap_error();
+        assert_eq!(cursor.byte_offset(), 1);
+        assert_eq!(error.message, "division by zero");
+        assert_eq!(error.operator, (1, "/"));
+
+        let mut reader = ParserInput::new_with_error::<&'static str>("3!");
+        let mut parser = ExpressionBuilder::new(number)
+            .postfix(1, read_char('!'), |_, _| Err("factorial"))
+            .build();
+
+        let (cursor, error) = parser(&mut reader).unwrap_err().unwrap_error();
+        assert_eq!(cursor.byte_offset(), 1);
+        assert_eq!(error, "factorial");
+    }
+
+    #[test]
+    fn test_max_precedence() {
+        let mut parser = ExpressionBuilder::new(number::<()>)
+            .prefix(u32::MAX, read_char('-'), |_, v| Ok(-v))
+            .infix(u32::MAX, Associativity::Left, read_char('-'), |l, _, r| {
+                Ok(l - r)
+            })
+            .infix(u32::MAX, Associativity::Right, read_char('^'), |l, _, r| {
+                Ok(l.pow(r as u32))
+            })
+            .build();
+
+        let mut reader = ParserInput::new_with_error("10-4-3");
+        assert_eq!(parser(&mut reader), Ok(3));
+        assert!(reader.is_end());
+
+        let mut reader = ParserInput::new_with_error("10--4-3");
+        assert_eq!(parser(&mut reader), Ok(11));
+        assert!(reader.is_end());
+
+        let mut reader = ParserInput::new_with_error("2^3^2");
+        assert_eq!(parser(&mut reader), Ok(512));
+        assert!(reader.is_end());
+    }
+}
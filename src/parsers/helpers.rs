@@ -1,20 +1,25 @@
+#[cfg(feature = "alloc")]
+use crate::error::ParserError;
+use crate::error::{ContextError, Expected};
 use crate::result::{ParserResult, ParserResultError};
-use crate::{Cursor, ParserInput};
+use crate::{Cursor, InputContent, ParserInput, Span};
 
 /// Restores the reader when a not found error is returned.
-pub fn not_found_restore<'a, P, C, R, Err>(
+pub fn not_found_restore<'a, P, C, R, Err, I: InputContent + ?Sized>(
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     move |reader| {
         let init_cursor = reader.save_cursor();
+        let errors_checkpoint = reader.errors_checkpoint();
 
         match parser(reader) {
             Ok(v) => Ok(v),
             Err(ParserResultError::NotFound) => {
                 reader.restore(init_cursor);
+                reader.rollback_errors(errors_checkpoint);
                 Err(ParserResultError::NotFound)
             }
             Err(e) => Err(e),
@@ -23,13 +28,13 @@ where
 }
 
 /// Maps the result of a parser into a new value.
-pub fn map_result<'a, P, M, C, R, Rf, Err>(
+pub fn map_result<'a, P, M, C, R, Rf, Err, I: ?Sized>(
     mut parser: P,
     mut mapper: M,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Rf, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rf, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    M: FnMut(&mut ParserInput<'a, Err, C>, R) -> Rf,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    M: FnMut(&mut ParserInput<'a, Err, C, I>, R) -> Rf,
 {
     move |reader| {
         let result = parser(reader)?;
@@ -39,13 +44,13 @@ where
 }
 
 /// Maps the result of a parser into a new `ParserResult`.
-pub fn and_then<'a, P, M, C, R, Rf, Err>(
+pub fn and_then<'a, P, M, C, R, Rf, Err, I: InputContent + ?Sized>(
     mut parser: P,
     mut mapper: M,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Rf, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rf, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    M: FnMut(&mut ParserInput<'a, Err, C>, R) -> ParserResult<Rf, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    M: FnMut(&mut ParserInput<'a, Err, C, I>, R) -> ParserResult<Rf, Err>,
 {
     not_found_restore(move |reader| {
         let result = parser(reader)?;
@@ -55,34 +60,43 @@ where
 }
 
 /// Applies a parser over the result of another one.
-pub fn map_parser<'a, O, P, C: Clone, R, Err>(
+/// The result can be of another input type, e.g. a text inside a binary input.
+pub fn map_parser<
+    'a,
+    O,
+    P,
+    C: Clone,
+    R,
+    Err,
+    I: InputContent + ?Sized,
+    I2: InputContent + ?Sized + 'a,
+>(
     mut origin: O,
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    O: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err>,
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    O: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<&'a I2, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I2>) -> ParserResult<R, Err>,
 {
     not_found_restore(move |reader| {
         let result = origin(reader)?;
-        let mut new_reader =
-            ParserInput::new_with_context_and_error(result, reader.context().clone());
+        let mut new_reader = ParserInput::from_content(result, reader.context().clone());
 
         parser(&mut new_reader)
     })
 }
 
 /// Applies a parser discarding its result and return the consumed content as result.
-pub fn consumed<'a, P, C, R, Err>(
+pub fn consumed<'a, P, C, R, Err, I: InputContent + ?Sized>(
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<&'a I, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     move |reader| {
         let init_cursor = reader.save_cursor();
         match parser(reader) {
-            Ok(_) => Ok(reader.substring_to_current(&init_cursor).content()),
+            Ok(_) => Ok(reader.content_to_current(&init_cursor)),
             Err(ParserResultError::NotFound) => {
                 reader.restore(init_cursor);
                 Err(ParserResultError::NotFound)
@@ -92,12 +106,76 @@ where
     }
 }
 
+/// Applies a parser returning its result together with the `Span` of the consumed content.
+/// See [Spanned](crate::Spanned).
+///
+/// # Example
+///
+/// ```
+/// # use jpar::ParserInput;
+/// # use jpar::characters::read_text;
+/// # use jpar::helpers::spanned;
+/// let mut reader = ParserInput::new("a\nbc");
+/// reader.read_text("a\n");
+///
+/// let (result, span) = spanned(read_text("bc"))(&mut reader).unwrap();
+/// assert_eq!(result, "bc");
+/// assert_eq!(span.start_cursor().line(), 2);
+/// assert_eq!(span.end_cursor().column(), 3);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn spanned<'a, P, C, R, Err, I: InputContent + ?Sized>(
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(R, Span<'a, I>), Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    move |reader| {
+        let init_cursor = reader.save_cursor();
+        match parser(reader) {
+            Ok(v) => Ok((v, reader.substring_to_current(&init_cursor))),
+            Err(ParserResultError::NotFound) => {
+                reader.restore(init_cursor);
+                Err(ParserResultError::NotFound)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Applies a parser returning its result together with the `Cursor` at which it starts.
+pub fn with_cursor<'a, P, C, R, Err, I: InputContent + ?Sized>(
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(R, Cursor), Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    move |reader| {
+        let init_cursor = reader.save_cursor();
+        match parser(reader) {
+            Ok(v) => Ok((v, reader.resolve_cursor(&init_cursor))),
+            Err(ParserResultError::NotFound) => {
+                reader.restore(init_cursor);
+                Err(ParserResultError::NotFound)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Returns the current `Cursor` without consuming any input.
+pub fn position<C, Err, I: InputContent + ?Sized>(
+    reader: &mut ParserInput<Err, C, I>,
+) -> ParserResult<Cursor, Err> {
+    Ok(reader.resolve_cursor(&reader.save_cursor()))
+}
+
 /// Applies a parser discarding its result.
-pub fn ignore_result<'a, P, C, R, Err>(
+pub fn ignore_result<'a, P, C, R, Err, I: InputContent + ?Sized>(
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<(), Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     not_found_restore(move |reader| {
         let _ = parser(reader)?;
@@ -106,27 +184,27 @@ where
 }
 
 /// Always succeeds with given value without consuming any input.
-pub fn value<'a, C, R: Clone, Err>(
+pub fn value<'a, C, R: Clone, Err, I: ?Sized>(
     value: R,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err> {
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> {
     move |_| Ok(value.clone())
 }
 
 /// Always succeeds with given value without consuming any input.
 /// The value is built dynamically.
-pub fn value_dyn<'a, C, R, Err, VFn>(
+pub fn value_dyn<'a, C, R, Err, VFn, I: ?Sized>(
     mut value_fn: VFn,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    VFn: FnMut(&mut ParserInput<'a, Err, C>) -> R,
+    VFn: FnMut(&mut ParserInput<'a, Err, C, I>) -> R,
 {
     move |reader| Ok(value_fn(reader))
 }
 
 /// Always fails with the given error without consuming any input.
-pub fn error<'a, C, R, Err: Clone>(
+pub fn error<'a, C, R, Err: Clone, I: ?Sized>(
     error: Err,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err> {
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> {
     move |reader| {
         Err(ParserResultError::Error((
             reader.save_cursor(),
@@ -137,11 +215,11 @@ pub fn error<'a, C, R, Err: Clone>(
 
 /// Always fails with the given error without consuming any input.
 /// The error is built dynamically.
-pub fn error_dyn<'a, C, R, Err, EFn>(
+pub fn error_dyn<'a, C, R, Err, EFn, I: ?Sized>(
     mut error_fn: EFn,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    EFn: FnMut(&mut ParserInput<'a, Err, C>) -> Err,
+    EFn: FnMut(&mut ParserInput<'a, Err, C, I>) -> Err,
 {
     move |reader| {
         Err(ParserResultError::Error((
@@ -152,13 +230,13 @@ where
 }
 
 /// Ensures that `parser` always success or returns an error.
-pub fn ensure<'a, P, C, R, Efn, Err>(
+pub fn ensure<'a, P, C, R, Efn, Err, I: ?Sized>(
     mut parser: P,
     mut error_fn: Efn,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    Efn: FnMut(&mut ParserInput<'a, Err, C>) -> Err,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    Efn: FnMut(&mut ParserInput<'a, Err, C, I>) -> Err,
 {
     move |reader| match parser(reader) {
         Ok(v) => Ok(v),
@@ -171,21 +249,199 @@ where
 }
 
 /// Applies a parser but allowing to recover in case of an error.
-pub fn recover<'a, P, C, R, Rfn, Err>(
+pub fn recover<'a, P, C, R, Rfn, Err, I: InputContent + ?Sized>(
     mut parser: P,
     mut recover_fn: Rfn,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    Rfn: FnMut(&mut ParserInput<'a, Err, C>, Cursor, Err) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    Rfn: FnMut(&mut ParserInput<'a, Err, C, I>, Cursor, Err) -> ParserResult<R, Err>,
 {
     not_found_restore(move |reader| match parser(reader) {
         Ok(v) => Ok(v),
-        Err(ParserResultError::NotFound) => Err(ParserResultError::NotFound),
         Err(ParserResultError::Error((cursor, e))) => recover_fn(reader, cursor, e),
+        Err(e) => Err(e),
+    })
+}
+
+/// Applies a parser but, in case of an error, reports it to the reader and skips the input
+/// until `sync_parser` is found, returning the placeholder built by `placeholder_fn` from the
+/// skipped fragment. This way a single parse can report every error of the input.
+///
+/// The content read by `sync_parser` is consumed, so it should be wrapped by
+/// [not_consume()](crate::combinator::not_consume) when it belongs to an outer parser, e.g. a `}`.
+/// The skipping also stops at the end of the input. If nothing can be skipped, the error
+/// of `parser` is returned instead of being reported, so a repetition of this parser always
+/// stops. The errors of `sync_parser` are returned too.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, ParserResult, ParserResultError};
+/// # use jpar::characters::{ascii_alpha1, read_char};
+/// # use jpar::helpers::{ensure, recover_until};
+/// # use jpar::sequence::{repeat, terminated};
+/// let mut statement = recover_until(
+///     terminated(ascii_alpha1, ensure(read_char(';'), |_| "missing semicolon")),
+///     read_char(';'),
+///     |span| span.content(),
+/// );
+///
+/// let mut reader = ParserInput::new_with_error("a;b c;d;");
+/// let result = repeat(.., &mut statement)(&mut reader);
+/// assert_eq!(result, Ok(vec!["a", "b c;", "d"]));
+///
+/// let (cursor, error) = &reader.errors()[0];
+/// assert_eq!(cursor.byte_offset(), 3);
+/// assert_eq!(*error, "missing semicolon");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn recover_until<'a, P, S, Rfn, C, R, Rs, Err, I: InputContent + ?Sized + 'a>(
+    mut parser: P,
+    mut sync_parser: S,
+    mut placeholder_fn: Rfn,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rs, Err>,
+    Rfn: FnMut(Span<'a, I>) -> R,
+{
+    not_found_restore(move |reader| {
+        let init_cursor = reader.save_cursor();
+        let (cursor, error) = match parser(reader) {
+            Ok(v) => return Ok(v),
+            Err(ParserResultError::Error(v)) => v,
+            Err(e) => return Err(e),
+        };
+
+        reader.restore(cursor.clone());
+
+        loop {
+            let sync_cursor = reader.save_cursor();
+            match sync_parser(reader) {
+                Ok(_) => break,
+                Err(ParserResultError::NotFound) => reader.restore(sync_cursor),
+                Err(e) => return Err(e),
+            }
+
+            if !reader.skip() {
+                break;
+            }
+        }
+
+        if reader.byte_offset() == init_cursor.byte_offset() {
+            return Err(ParserResultError::Error((cursor, error)));
+        }
+
+        reader.report_error(cursor, error);
+        Ok(placeholder_fn(reader.substring_to_current(&init_cursor)))
     })
 }
 
+/// Records `expected` in the reader when `parser` is not found.
+/// See [ParserInput::set_expected_tracking()].
+pub fn expecting<'a, P, C, R, Err, I: InputContent + ?Sized>(
+    expected: Expected<'a>,
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    move |reader| {
+        let init_cursor = reader.save_cursor();
+
+        match parser(reader) {
+            Ok(v) => Ok(v),
+            Err(ParserResultError::NotFound) => {
+                reader.restore(init_cursor);
+                reader.expect(expected.clone());
+                Err(ParserResultError::NotFound)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Names the rule parsed by `parser`.
+///
+/// When `parser` returns an error, `label` and the start position are added to the error context chain.
+/// When it is not found, the expectations it recorded at its start position are replaced by `label`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// # use jpar::ParserInput;
+/// # use jpar::branch::alternative;
+/// # use jpar::helpers::label;
+/// # use jpar::characters::read_text;
+/// let mut reader = ParserInput::new("maybe");
+/// reader.set_expected_tracking(true);
+///
+/// let mut parser = label("boolean", alternative((read_text("true"), read_text("false"))));
+/// assert!(parser(&mut reader).is_err());
+/// assert_eq!(reader.furthest_error().unwrap().to_string(), "expected boolean at 1:1");
+/// # }
+/// ```
+pub fn label<'a, P, C, R, Err, I: InputContent + ?Sized>(
+    label: &'a str,
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    Err: ContextError<'a>,
+{
+    move |reader| {
+        let init_cursor = reader.save_cursor();
+        let checkpoint = reader.expected_checkpoint();
+
+        match parser(reader) {
+            Ok(v) => Ok(v),
+            Err(ParserResultError::NotFound) => {
+                reader.restore(init_cursor);
+                reader.rollback_expected(checkpoint);
+                reader.expect(Expected::Label(label));
+                Err(ParserResultError::NotFound)
+            }
+            Err(ParserResultError::Error((cursor, mut error))) => {
+                error.add_context(label, reader.resolve_cursor(&init_cursor));
+                Err(ParserResultError::Error((cursor, error)))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Converts a not found result of `parser` into an error built from the
+/// furthest error recorded by the reader. If there is none, the error is placed
+/// at the current position without any expectation.
+/// See [ParserInput::set_expected_tracking()].
+#[cfg(feature = "alloc")]
+pub fn expected_error<'a, P, C, R, Err, I: InputContent + ?Sized>(
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    Err: From<ParserError<'a>>,
+{
+    move |reader| match parser(reader) {
+        Ok(v) => Ok(v),
+        Err(ParserResultError::NotFound) => {
+            let error = match reader.take_furthest_error() {
+                Some(v) => v,
+                None => ParserError::new(reader.resolve_cursor(&reader.save_cursor())),
+            };
+
+            Err(ParserResultError::Error((
+                error.cursor().clone(),
+                error.into(),
+            )))
+        }
+        Err(e) => Err(e),
+    }
+}
+
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
@@ -196,7 +452,7 @@ mod test {
         ascii_alpha1, ascii_alpha_quantified, read_any, read_any_quantified, read_text,
     };
     use crate::parsers::sequence::tuple;
-    use crate::ParserResultError;
+    use crate::{ParserResultError, Spanned};
 
     use super::*;
 
@@ -274,6 +530,44 @@ mod test {
         assert_eq!(result, Ok("Test"));
     }
 
+    #[test]
+    fn test_spanned() {
+        let mut reader = ParserInput::new("Test\nTest");
+        let mut parser = spanned(tuple((read_text("Te"), read_text("st"))));
+
+        let (result, span) = parser(&mut reader).unwrap();
+        assert_eq!(result, ("Te", "st"));
+        assert_eq!(span.content(), "Test");
+        assert_eq!(span.start_cursor(), &Cursor::new(0, 0, 1, 1));
+        assert_eq!(span.end_cursor(), &Cursor::new(4, 4, 1, 5));
+
+        assert_eq!(parser(&mut reader), Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 4);
+
+        reader.read_text("\n");
+        reader.set_lazy_tracking(true);
+        let mut parser = map_result(spanned(read_text("Test")), |_, v| Spanned::from(v));
+        let result = parser(&mut reader).unwrap();
+        assert_eq!(*result, "Test");
+        assert_eq!(result.span().start_cursor(), &Cursor::new(5, 5, 2, 1));
+        assert_eq!(result.map(|v| v.len()).into_value(), 4);
+    }
+
+    #[test]
+    fn test_with_cursor_position() {
+        let mut reader = ParserInput::new("Test\nTest");
+        reader.set_lazy_tracking(true);
+        reader.read_text("Test\n");
+
+        let cursor = position(&mut reader).unwrap();
+        assert_eq!(cursor, Cursor::new(5, 5, 2, 1));
+        assert_eq!(reader.byte_offset(), 5);
+
+        let mut parser = with_cursor(read_text("Test"));
+        assert_eq!(parser(&mut reader), Ok(("Test", cursor)));
+        assert_eq!(parser(&mut reader), Err(ParserResultError::NotFound));
+    }
+
     #[test]
     fn test_ignore_result() {
         let mut reader = ParserInput::new("Test 123");
@@ -360,4 +654,220 @@ mod test {
         let result = parser(&mut reader);
         assert_eq!(result, Ok("recover"));
     }
+
+    #[test]
+    fn test_expecting() {
+        let mut reader = ParserInput::new("This is a test");
+        let mut parser = expecting(
+            Expected::Label("word"),
+            tuple((read_text("This"), read_text("!"))),
+        );
+
+        let result = parser(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 0);
+    }
+
+    #[test]
+    fn test_label() {
+        let mut reader = ParserInput::new("This is a test");
+        let mut parser = label("sentence", tuple((read_text("This"), read_text("!"))));
+
+        let result = parser(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 0);
+
+        let mut parser = label("sentence", read_text("This"));
+        let result = parser(&mut reader);
+        assert_eq!(result, Ok("This"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod test_alloc {
+    use crate::parsers::branch::alternative;
+    use crate::parsers::characters::{ascii_alpha1, read_any_of1, read_char, read_text};
+    use crate::parsers::combinator::not_consume;
+    use crate::parsers::sequence::{
+        delimited, repeat_and_count, repeat_separated, terminated, tuple,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_expecting() {
+        let mut reader = ParserInput::new("This is a test");
+        reader.set_expected_tracking(true);
+
+        let mut parser = expecting(Expected::Label("word"), read_any_of1(|_, c| c == 'x'));
+        let result = parser(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(
+            reader.furthest_error().unwrap().expected(),
+            &[Expected::Label("word")]
+        );
+    }
+
+    #[test]
+    fn test_expected_error() {
+        let mut reader = ParserInput::new_with_error::<ParserError>("null");
+        reader.set_expected_tracking(true);
+
+        let mut parser = expected_error(alternative((read_text("true"), read_text("false"))));
+        let (cursor, error) = parser(&mut reader).unwrap_err().unwrap_error();
+        assert_eq!(cursor.byte_offset(), 0);
+        assert_eq!(error.to_string(), "expected one of `true`, `false` at 1:1");
+
+        // Without tracking.
+        let mut reader = ParserInput::new_with_error::<ParserError>("null");
+        let (_, error) = parser(&mut reader).unwrap_err().unwrap_error();
+        assert_eq!(error.to_string(), "unexpected input at 1:1");
+    }
+
+    #[test]
+    fn test_label_expected() {
+        let mut reader = ParserInput::new("maybe");
+        reader.set_expected_tracking(true);
+
+        let mut parser = alternative((
+            label(
+                "boolean",
+                alternative((read_text("true"), read_text("false"))),
+            ),
+            read_text("null"),
+            label("number", read_any_of1(|_, c| c.is_ascii_digit())),
+        ));
+
+        let result = parser(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(
+            reader.furthest_error().unwrap().to_string(),
+            "expected one of boolean, `null`, number at 1:1"
+        );
+
+        // A labelled parser that fails further keeps the inner expectations.
+        let mut reader = ParserInput::new("(a");
+        reader.set_expected_tracking(true);
+
+        let mut parser = label(
+            "group",
+            tuple((read_char('('), read_char('a'), read_char(')'))),
+        );
+        let result = parser(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(
+            reader.furthest_error().unwrap().to_string(),
+            "expected `)` at 1:3"
+        );
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn member<'a>(
+        reader: &mut ParserInput<'a, ParserError<'a>>,
+    ) -> ParserResult<&'a str, ParserError<'a>> {
+        label(
+            "object member",
+            expected_error(delimited(read_char('"'), read_text("key"), read_char('"'))),
+        )(reader)
+    }
+
+    #[test]
+    fn test_label_context() {
+        let mut reader = ParserInput::new_with_error::<ParserError>("[\"key\", \"kei\"]");
+        reader.set_expected_tracking(true);
+
+        let mut parser = delimited(
+            read_char('['),
+            repeat_separated(.., label("array element", member), read_text(", ")),
+            read_char(']'),
+        );
+
+        let (cursor, error) = parser(&mut reader).unwrap_err().unwrap_error();
+        assert_eq!(cursor.byte_offset(), 9);
+        assert_eq!(
+            error
+                .context()
+                .iter()
+                .map(|(l, c)| (*l, c.byte_offset()))
+                .collect::<Vec<_>>(),
+            vec![("object member", 8), ("array element", 8)]
+        );
+        assert_eq!(
+            error.to_string(),
+            "expected `key` at 1:10, while parsing array element → while parsing object member"
+        );
+    }
+
+    #[test]
+    fn test_recover_until() {
+        let mut item = recover_until(
+            ensure(read_any_of1(|_, c| c.is_ascii_digit()), |_| {
+                "expected number"
+            }),
+            not_consume(read_char(']')),
+            |span| span.content(),
+        );
+        let mut reader = ParserInput::new_with_error("[1,x,3][y");
+        let mut parser = |reader: &mut ParserInput<'static, &'static str>| {
+            delimited(
+                read_char('['),
+                repeat_separated(.., &mut item, read_char(',')),
+                read_char(']'),
+            )(reader)
+        };
+
+        assert_eq!(parser(&mut reader), Ok(vec!["1", "x,3"]));
+        assert_eq!(reader.errors().len(), 1);
+        assert_eq!(reader.errors()[0].0.byte_offset(), 3);
+        assert_eq!(reader.errors()[0].1, "expected number");
+
+        // The skipping stops at the end of the input and the errors of the
+        // failed branch are discarded.
+        assert_eq!(parser(&mut reader), Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 7);
+
+        let errors = reader.take_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0.byte_offset(), 3);
+        assert!(reader.errors().is_empty());
+
+        // Only the errors of the successful alternative are kept.
+        let mut reader = ParserInput::new_with_error("[y");
+        let mut parser = alternative((&mut parser, map_result(read_text("[y"), |_, v| vec![v])));
+        assert_eq!(parser(&mut reader), Ok(vec!["[y"]));
+        assert!(reader.errors().is_empty());
+    }
+
+    #[test]
+    fn test_recover_until_without_progress() {
+        let mut statement = recover_until(
+            ensure(terminated(ascii_alpha1, read_char(';')), |_| {
+                "expected statement"
+            }),
+            read_char(';'),
+            |span| span.content(),
+        );
+
+        // At the end of the input nothing can be skipped so the repetition stops.
+        let mut reader = ParserInput::new_with_error("a;b");
+        let result = repeat_and_count(.., &mut statement)(&mut reader);
+        let (cursor, error) = result.unwrap_err().unwrap_error();
+        assert_eq!(cursor.byte_offset(), 3);
+        assert_eq!(error, "expected statement");
+        assert_eq!(reader.errors().len(), 1);
+        assert_eq!(reader.errors()[0].0.byte_offset(), 2);
+
+        // The errors of the synchronization parser are returned.
+        let mut parser = recover_until(
+            ensure(ascii_alpha1, |_| "expected word"),
+            ensure(read_char(';'), |_| "expected semicolon"),
+            |span| span.content(),
+        );
+        let mut reader = ParserInput::new_with_error("1;");
+        let (cursor, error) = parser(&mut reader).unwrap_err().unwrap_error();
+        assert_eq!(cursor.byte_offset(), 0);
+        assert_eq!(error, "expected semicolon");
+        assert!(reader.errors().is_empty());
+    }
 }
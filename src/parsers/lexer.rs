@@ -0,0 +1,322 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::input::{Cursor, InputContent, Token};
+use crate::result::{ParserResult, ParserResultError};
+use crate::ParserInput;
+
+type BoxedParser<'a, 'p, Err, C, I> =
+    Box<dyn FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err> + 'p>;
+
+struct LexerRule<'a, 'p, K, Err, C, I: ?Sized> {
+    kind: Option<K>,
+    parser: BoxedParser<'a, 'p, Err, C, I>,
+}
+
+/// A lexer that splits a content into [Token]s following a table of rules.
+///
+/// At every position all the rules are tested and the one that matches the longest
+/// fragment produces a token of its kind. Ties are broken in favour of the rule registered
+/// first, so keywords must be registered before identifiers to take precedence over them
+/// while an identifier that starts with a keyword, e.g. `lettuce`, is still read as a whole.
+/// The fragments matched by skip rules, e.g. whitespaces or comments, produce no token.
+/// Rules that match an empty fragment are ignored.
+///
+/// The resulting tokens can be parsed with the same combinators as any other content.
+/// See [tokens](crate::tokens).
+///
+/// # Example
+///
+/// ```
+/// # use jpar::ParserInput;
+/// # use jpar::characters::{ascii_alpha1, read_char, read_text, ucd_whitespace1};
+/// # use jpar::lexer::Lexer;
+/// # use jpar::sequence::tuple;
+/// # use jpar::tokens::read_kind;
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Kind {
+///     Let,
+///     Identifier,
+///     Equals,
+/// }
+///
+/// let mut lexer = Lexer::new()
+///     .skip(ucd_whitespace1)
+///     .rule(Kind::Let, read_text("let"))
+///     .rule(Kind::Identifier, ascii_alpha1)
+///     .rule(Kind::Equals, read_char('='));
+///
+/// let mut reader = ParserInput::new("let lettuce = b");
+/// let tokens = lexer.tokenize(&mut reader).unwrap();
+/// assert_eq!(tokens.len(), 4);
+/// assert_eq!(tokens[1].kind(), &Kind::Identifier);
+/// assert_eq!(tokens[3].span().start_cursor().column(), 15);
+///
+/// let mut reader = ParserInput::<(), (), [_]>::from_content(&tokens[..], ());
+/// let mut parser = tuple((
+///     read_kind(Kind::Let),
+///     read_kind(Kind::Identifier),
+///     read_kind(Kind::Equals),
+///     read_kind(Kind::Identifier),
+/// ));
+///
+/// let (_, name, _, value) = parser(&mut reader).unwrap();
+/// assert_eq!(name.span().content(), "lettuce");
+/// assert_eq!(value.span().content(), "b");
+/// ```
+pub struct Lexer<'a, 'p, K, Err = (), C = (), I: ?Sized = str> {
+    rules: Vec<LexerRule<'a, 'p, K, Err, C, I>>,
+}
+
+impl<'a, 'p, K: Clone, Err, C, I: InputContent + ?Sized> Lexer<'a, 'p, K, Err, C, I> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `Lexer` without rules.
+    pub fn new() -> Lexer<'a, 'p, K, Err, C, I> {
+        Lexer { rules: Vec::new() }
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Registers a rule that produces a token of `kind` for the fragments read by `parser`.
+    pub fn rule<P, R>(mut self, kind: K, mut parser: P) -> Self
+    where
+        P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> + 'p,
+    {
+        self.rules.push(LexerRule {
+            kind: Some(kind),
+            parser: Box::new(move |reader| parser(reader).map(|_| ())),
+        });
+        self
+    }
+
+    /// Registers a rule that discards the fragments read by `parser`, e.g. whitespaces or comments.
+    pub fn skip<P, R>(mut self, mut parser: P) -> Self
+    where
+        P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> + 'p,
+    {
+        self.rules.push(LexerRule {
+            kind: None,
+            parser: Box::new(move |reader| parser(reader).map(|_| ())),
+        });
+        self
+    }
+
+    /// Reads all the tokens until the end of the content.
+    ///
+    /// If no rule matches at some position, it returns `NotFound` leaving the reader
+    /// at that position.
+    pub fn tokenize(
+        &mut self,
+        reader: &mut ParserInput<'a, Err, C, I>,
+    ) -> ParserResult<Vec<Token<'a, K, I>>, Err> {
+        self.tokens(reader).collect()
+    }
+
+    /// Builds an iterator that reads the tokens one by one until the end of the content.
+    ///
+    /// If no rule matches at some position, the iterator returns `NotFound` leaving the
+    /// reader at that position, and finishes after it or any other error.
+    pub fn tokens<'l>(
+        &'l mut self,
+        reader: &'l mut ParserInput<'a, Err, C, I>,
+    ) -> Tokens<'a, 'p, 'l, K, Err, C, I> {
+        Tokens {
+            lexer: self,
+            reader,
+            finished: false,
+        }
+    }
+
+    /// Reads the next token skipping the fragments of the skip rules.
+    fn next_token(
+        &mut self,
+        reader: &mut ParserInput<'a, Err, C, I>,
+    ) -> Option<ParserResult<Token<'a, K, I>, Err>> {
+        while !reader.is_end() {
+            let init_cursor = reader.save_cursor();
+            let mut longest: Option<(usize, Cursor)> = None;
+
+            for (index, rule) in self.rules.iter_mut().enumerate() {
+                match (rule.parser)(reader) {
+                    Ok(()) => {
+                        let offset = reader.byte_offset();
+                        let is_longer = match &longest {
+                            Some((_, cursor)) => offset > cursor.byte_offset(),
+                            None => offset > init_cursor.byte_offset(),
+                        };
+
+                        if is_longer {
+                            longest = Some((index, reader.save_cursor()));
+                        }
+                    }
+                    Err(ParserResultError::NotFound) => {}
+                    Err(e) => return Some(Err(e)),
+                }
+
+                reader.restore(init_cursor.clone());
+            }
+
+            let (index, cursor) = match longest {
+                Some(v) => v,
+                None => return Some(Err(ParserResultError::NotFound)),
+            };
+
+            reader.restore(cursor);
+
+            if let Some(kind) = &self.rules[index].kind {
+                let span = reader.substring_to_current(&init_cursor);
+                return Some(Ok(Token::new(kind.clone(), span)));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, 'p, K: Clone, Err, C, I: InputContent + ?Sized> Default for Lexer<'a, 'p, K, Err, C, I> {
+    fn default() -> Self {
+        Lexer::new()
+    }
+}
+
+/// An iterator over the tokens of a content. See [Lexer::tokens()].
+pub struct Tokens<'a, 'p, 'l, K, Err, C, I: ?Sized> {
+    lexer: &'l mut Lexer<'a, 'p, K, Err, C, I>,
+    reader: &'l mut ParserInput<'a, Err, C, I>,
+    finished: bool,
+}
+
+impl<'a, 'p, 'l, K: Clone, Err, C, I: InputContent + ?Sized> Iterator
+    for Tokens<'a, 'p, 'l, K, Err, C, I>
+{
+    type Item = ParserResult<Token<'a, K, I>, Err>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let result = self.lexer.next_token(self.reader);
+        if !matches!(result, Some(Ok(_))) {
+            self.finished = true;
+        }
+
+        result
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::parsers::branch::alternative;
+    use crate::parsers::characters::{
+        ascii_alpha1, decimal_digit1, read_char, read_none_of0, read_text, ucd_whitespace1,
+    };
+    use crate::parsers::helpers::map_result;
+    use crate::parsers::sequence::{repeat_separated, tuple};
+    use crate::parsers::tokens::{read_kind, read_token, read_token_if};
+    use crate::parsers::verifiers::char_verifier;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+    enum Kind {
+        Let,
+        Identifier,
+        Number,
+        Plus,
+        Comma,
+    }
+
+    fn lexer<'a>() -> Lexer<'a, 'a, Kind> {
+        Lexer::new()
+            .skip(ucd_whitespace1)
+            .skip(tuple((read_text("//"), read_none_of0(char_verifier('\n')))))
+            .rule(Kind::Let, read_text("let"))
+            .rule(Kind::Identifier, ascii_alpha1)
+            .rule(Kind::Number, decimal_digit1)
+            .rule(Kind::Plus, read_char('+'))
+            .rule(Kind::Comma, read_char(','))
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let mut reader = ParserInput::new("a + 12 // comment\n+b");
+        let tokens = lexer().tokenize(&mut reader).unwrap();
+
+        let kinds: Vec<_> = tokens.iter().map(|v| *v.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::Identifier,
+                Kind::Plus,
+                Kind::Number,
+                Kind::Plus,
+                Kind::Identifier
+            ]
+        );
+
+        assert_eq!(tokens[2].span().content(), "12");
+        assert_eq!(tokens[4].span().start_cursor().line(), 2);
+        assert_eq!(tokens[4].span().start_cursor().column(), 2);
+        assert!(reader.is_end());
+
+        let mut reader = ParserInput::new("   ");
+        assert_eq!(lexer().tokenize(&mut reader), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_longest_match() {
+        let mut reader = ParserInput::new("let lettuce let+letter");
+        let tokens = lexer().tokenize(&mut reader).unwrap();
+
+        let tokens: Vec<_> = tokens
+            .iter()
+            .map(|v| (*v.kind(), v.span().content()))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (Kind::Let, "let"),
+                (Kind::Identifier, "lettuce"),
+                (Kind::Let, "let"),
+                (Kind::Plus, "+"),
+                (Kind::Identifier, "letter")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_not_found() {
+        let mut lexer = lexer();
+        let mut reader = ParserInput::new("a ? b");
+        let mut tokens = lexer.tokens(&mut reader);
+
+        assert_eq!(tokens.next().unwrap().unwrap().span().content(), "a");
+        assert_eq!(tokens.next(), Some(Err(ParserResultError::NotFound)));
+        assert_eq!(tokens.next(), None);
+        assert_eq!(reader.byte_offset(), 2);
+    }
+
+    #[test]
+    fn test_parse_tokens() {
+        let mut reader = ParserInput::new("a + 1, 2 + b");
+        let tokens = lexer().tokenize(&mut reader).unwrap();
+        let mut reader = ParserInput::<(), (), [_]>::from_content(&tokens[..], ());
+
+        let operand = alternative((read_kind(Kind::Identifier), read_kind(Kind::Number)));
+        let sum = map_result(
+            tuple((operand, read_kind(Kind::Plus), read_token)),
+            |_, (l, _, r)| (l.span().content(), r.span().content()),
+        );
+        let mut parser = repeat_separated(1.., sum, read_token_if(|v| v.kind() == &Kind::Comma));
+
+        assert_eq!(parser(&mut reader), Ok(vec![("a", "1"), ("2", "b")]));
+        assert!(reader.is_end());
+        assert_eq!(reader.byte_offset(), 7);
+    }
+}
@@ -0,0 +1,549 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::result::{ParserResult, ParserResultError};
+use crate::{Cursor, InputContent, ParserInput};
+
+/// A cache of the results of a parser indexed by the byte offset at which they started,
+/// used to avoid re-parsing the same fragment when backtracking (packrat parsing).
+/// Every result is stored along with the errors that the parser reported.
+///
+/// The table is bound to the first input it is used with. Using it with another
+/// input clears it, even if it reads the same content, so it can be reused between parses.
+#[derive(Debug, Clone)]
+pub struct MemoTable<R, Err = ()> {
+    input: Option<usize>,
+    entries: BTreeMap<usize, MemoEntry<R, Err>>,
+}
+
+/// A cached result, the position at which the parser stopped and the errors it reported.
+type MemoEntry<R, Err> = (ParserResult<R, Err>, Cursor, Vec<(Cursor, Err)>);
+
+impl<R, Err> MemoTable<R, Err> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new empty `MemoTable`.
+    pub fn new() -> MemoTable<R, Err> {
+        MemoTable {
+            input: None,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there is any cached result or not.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Removes all the cached results.
+    pub fn clear(&mut self) {
+        self.input = None;
+        self.entries.clear();
+    }
+
+    /// Gets the cached result at `offset` clearing the table if the input identified
+    /// by `input` is not the one it is bound to.
+    fn get(&mut self, input: usize, offset: usize) -> Option<MemoEntry<R, Err>>
+    where
+        R: Clone,
+        Err: Clone,
+    {
+        if self.input != Some(input) {
+            self.clear();
+            self.input = Some(input);
+        }
+
+        self.entries.get(&offset).cloned()
+    }
+
+    /// Caches the `result` of a parser that started at `offset`, stopped at `cursor`
+    /// and reported `errors`.
+    /// Incomplete results are not cached because they depend on the received content.
+    fn insert(
+        &mut self,
+        offset: usize,
+        result: &ParserResult<R, Err>,
+        cursor: Cursor,
+        errors: Vec<(Cursor, Err)>,
+    ) where
+        R: Clone,
+        Err: Clone,
+    {
+        if !matches!(result, Err(ParserResultError::Incomplete(_))) {
+            self.entries
+                .insert(offset, (result.clone(), cursor, errors));
+        }
+    }
+}
+
+impl<R, Err> Default for MemoTable<R, Err> {
+    fn default() -> Self {
+        MemoTable::new()
+    }
+}
+
+/// Caches the results of `parser`, including errors and `NotFound`, per start offset
+/// so that it is executed at most once at each position of the input.
+///
+/// The cache belongs to the returned parser, so it must be built once and reused.
+/// Use [memoize_in()] when the parser is rebuilt in every call, e.g. in recursive rules.
+///
+/// The errors reported by `parser`, e.g. through [recover_until()](crate::helpers::recover_until),
+/// are reported again when a cached result is used.
+///
+/// **Note**: cached results do not record the expectations of `parser` again, nor collect
+/// its comments, nor repeat its changes to the context. Do not memoize parsers that skip
+/// trivia while the comment collection is enabled, or that depend on those changes.
+///
+/// # Example
+///
+/// ```
+/// # use std::cell::Cell;
+/// # use jpar::ParserInput;
+/// # use jpar::characters::ascii_alpha1;
+/// # use jpar::memo::memoize;
+/// let calls = Cell::new(0);
+/// let mut word = memoize(|reader: &mut ParserInput<'static>| {
+///     calls.set(calls.get() + 1);
+///     ascii_alpha1(reader)
+/// });
+///
+/// let mut reader = ParserInput::new("test");
+/// let start = reader.save_cursor();
+/// assert_eq!(word(&mut reader), Ok("test"));
+///
+/// reader.restore(start);
+/// assert_eq!(word(&mut reader), Ok("test"));
+/// assert!(reader.is_end());
+/// assert_eq!(calls.get(), 1);
+/// ```
+pub fn memoize<'a, P, C, R: Clone, Err: Clone, I: InputContent + ?Sized>(
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    let mut table = MemoTable::new();
+
+    move |reader| {
+        let offset = reader.byte_offset();
+        if let Some((result, cursor, errors)) = table.get(reader.id(), offset) {
+            replay(reader, cursor, errors);
+            return result;
+        }
+
+        let errors_checkpoint = reader.errors_checkpoint();
+        let result = parser(reader);
+        let errors = reported_since(reader, errors_checkpoint);
+        table.insert(offset, &result, reader.save_cursor(), errors);
+
+        result
+    }
+}
+
+/// Same as [memoize()] but the cache is the [MemoTable] returned by `table` from
+/// the context of the input, so it lasts as long as the context.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, ParserResult};
+/// # use jpar::characters::ascii_alpha1;
+/// # use jpar::memo::{memoize_in, MemoTable};
+/// fn word<'a>(reader: &mut ParserInput<'a, (), MemoTable<&'a str>>) -> ParserResult<&'a str> {
+///     memoize_in(|context: &mut MemoTable<&'a str>| context, ascii_alpha1)(reader)
+/// }
+///
+/// let mut reader = ParserInput::new_with_context("test", MemoTable::new());
+/// let start = reader.save_cursor();
+/// assert_eq!(word(&mut reader), Ok("test"));
+///
+/// reader.restore(start);
+/// assert_eq!(word(&mut reader), Ok("test"));
+/// assert_eq!(reader.context().len(), 1);
+/// ```
+pub fn memoize_in<'a, T, P, C, R: Clone, Err: Clone, I: InputContent + ?Sized>(
+    mut table: T,
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
+where
+    T: FnMut(&mut C) -> &mut MemoTable<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    move |reader| {
+        let offset = reader.byte_offset();
+        let input = reader.id();
+        if let Some((result, cursor, errors)) = table(reader.context_mut()).get(input, offset) {
+            replay(reader, cursor, errors);
+            return result;
+        }
+
+        let errors_checkpoint = reader.errors_checkpoint();
+        let result = parser(reader);
+        let cursor = reader.save_cursor();
+        let errors = reported_since(reader, errors_checkpoint);
+        table(reader.context_mut()).insert(offset, &result, cursor, errors);
+
+        result
+    }
+}
+
+/// The errors reported to `reader` after `checkpoint`.
+fn reported_since<Err: Clone, C, I: InputContent + ?Sized>(
+    reader: &ParserInput<Err, C, I>,
+    checkpoint: usize,
+) -> Vec<(Cursor, Err)> {
+    reader
+        .errors()
+        .get(checkpoint..)
+        .map(<[_]>::to_vec)
+        .unwrap_or_default()
+}
+
+/// Moves `reader` to where a cached parser stopped and reports its `errors` again.
+fn replay<Err, C, I: InputContent + ?Sized>(
+    reader: &mut ParserInput<Err, C, I>,
+    cursor: Cursor,
+    errors: Vec<(Cursor, Err)>,
+) {
+    reader.restore(cursor);
+
+    for (cursor, error) in errors {
+        reader.report_error(cursor, error);
+    }
+}
+
+/// The state of a left-recursive rule: the seeds that are growing at each offset.
+///
+/// See [left_recursive()].
+#[derive(Debug, Clone)]
+pub struct SeedTable<R, Err = ()> {
+    seeds: BTreeMap<usize, (ParserResult<R, Err>, Cursor)>,
+}
+
+impl<R, Err> SeedTable<R, Err> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new empty `SeedTable`.
+    pub fn new() -> SeedTable<R, Err> {
+        SeedTable {
+            seeds: BTreeMap::new(),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Whether there is any seed growing or not.
+    pub fn is_empty(&self) -> bool {
+        self.seeds.is_empty()
+    }
+}
+
+impl<R, Err> Default for SeedTable<R, Err> {
+    fn default() -> Self {
+        SeedTable::new()
+    }
+}
+
+/// Allows `parser` to be a directly left-recursive rule, e.g. `expr := expr '-' num | num`.
+///
+/// When the rule calls itself at the same offset it gets the last result found (the seed),
+/// initially `NotFound`, and `parser` is executed again while it consumes more input
+/// than the previous seed. The [SeedTable] returned by `table` from the context
+/// stores the seeds while they grow and must be exclusive of this rule.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, ParserResult};
+/// # use jpar::branch::alternative;
+/// # use jpar::characters::read_char;
+/// # use jpar::memo::{left_recursive, SeedTable};
+/// # use jpar::numbers::unsigned;
+/// # use jpar::sequence::tuple;
+/// fn expr(reader: &mut ParserInput<(), SeedTable<i64>>) -> ParserResult<i64> {
+///     left_recursive(
+///         |context: &mut SeedTable<i64>| context,
+///         alternative((
+///             |r: &mut ParserInput<(), SeedTable<i64>>| {
+///                 let (left, _, right) = tuple((expr, read_char('-'), unsigned::<i64, _, _>()))(r)?;
+///                 Ok(left - right)
+///             },
+///             unsigned(),
+///         )),
+///     )(reader)
+/// }
+///
+/// let mut reader = ParserInput::new_with_context("10-4-3", SeedTable::new());
+/// assert_eq!(expr(&mut reader), Ok(3));
+/// assert!(reader.is_end());
+/// ```
+pub fn left_recursive<'a, T, P, C, R: Clone, Err: Clone, I: InputContent + ?Sized>(
+    mut table: T,
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
+where
+    T: FnMut(&mut C) -> &mut SeedTable<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    move |reader| {
+        let init_cursor = reader.save_cursor();
+        let offset = init_cursor.byte_offset();
+
+        // Recursive call: return the current seed.
+        if let Some((result, cursor)) = table(reader.context_mut()).seeds.get(&offset).cloned() {
+            reader.restore(cursor);
+            return result;
+        }
+
+        table(reader.context_mut()).seeds.insert(
+            offset,
+            (Err(ParserResultError::NotFound), init_cursor.clone()),
+        );
+
+        loop {
+            reader.restore(init_cursor.clone());
+
+            let result = parser(reader);
+            let cursor = reader.save_cursor();
+            let seeds = &mut table(reader.context_mut()).seeds;
+            let (seed, seed_cursor) = seeds.get(&offset).unwrap();
+
+            match result {
+                Ok(_) if seed.is_err() || cursor.byte_offset() > seed_cursor.byte_offset() => {
+                    seeds.insert(offset, (result, cursor));
+                }
+                Ok(_) | Err(ParserResultError::NotFound) => break,
+                Err(e) => {
+                    seeds.remove(&offset);
+                    return Err(e);
+                }
+            }
+        }
+
+        let (result, cursor) = table(reader.context_mut()).seeds.remove(&offset).unwrap();
+        reader.restore(cursor);
+
+        result
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::parsers::branch::alternative;
+    use crate::parsers::characters::{read_any_of, read_any_of1, read_char, read_text};
+    use crate::parsers::helpers::recover_until;
+    use crate::parsers::sequence::tuple;
+    use crate::result::Needed;
+
+    use super::*;
+
+    struct Context<'a> {
+        calls: usize,
+        table: MemoTable<&'a str, &'static str>,
+    }
+
+    fn word<'a>(
+        reader: &mut ParserInput<'a, &'static str, Context<'a>>,
+    ) -> ParserResult<&'a str, &'static str> {
+        memoize_in(
+            |context: &mut Context<'a>| &mut context.table,
+            |reader: &mut ParserInput<'a, &'static str, Context<'a>>| {
+                reader.context_mut().calls += 1;
+                read_text("test")(reader)
+            },
+        )(reader)
+    }
+
+    #[test]
+    fn test_memoize_in() {
+        let context = Context {
+            calls: 0,
+            table: MemoTable::new(),
+        };
+        let mut reader = ParserInput::new_with_context_and_error("test!", context);
+        let result = alternative((
+            tuple((word, read_char('?'))),
+            tuple((word, read_char('.'))),
+            tuple((word, read_char('!'))),
+        ))(&mut reader);
+
+        assert_eq!(result, Ok(("test", '!')));
+        assert_eq!(reader.context().calls, 1);
+        assert_eq!(reader.context().table.len(), 1);
+
+        let result = word(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 5);
+        assert_eq!(reader.context().calls, 2);
+        assert_eq!(reader.context().table.len(), 2);
+
+        // Another input clears the table.
+        let context = reader.into_context();
+        let mut reader = ParserInput::new_with_context_and_error("test", context);
+        assert_eq!(word(&mut reader), Ok("test"));
+        assert_eq!(reader.context().calls, 3);
+        assert_eq!(reader.context().table.len(), 1);
+    }
+
+    #[test]
+    fn test_memoize_in_reused_buffer() {
+        fn length(reader: &mut ParserInput<(), MemoTable<usize>>) -> ParserResult<usize> {
+            memoize_in(
+                |table: &mut MemoTable<usize>| table,
+                |reader: &mut ParserInput<(), MemoTable<usize>>| {
+                    read_any_of1(|_, c| c.is_ascii_alphabetic())(reader).map(|v| v.len())
+                },
+            )(reader)
+        }
+
+        let mut buffer = String::from("abc");
+        let mut reader = ParserInput::new_with_context(buffer.as_str(), MemoTable::new());
+        assert_eq!(length(&mut reader), Ok(3));
+
+        // A new input over the same memory with the same length clears the table.
+        let table = reader.into_context();
+        buffer.clear();
+        buffer.push_str("a!c");
+
+        let mut reader = ParserInput::new_with_context(buffer.as_str(), table);
+        assert_eq!(length(&mut reader), Ok(1));
+        assert_eq!(reader.context().len(), 1);
+    }
+
+    #[test]
+    fn test_memoize_errors() {
+        let mut calls = 0;
+        let mut parser = memoize(|reader: &mut ParserInput<&'static str>| {
+            calls += 1;
+            reader.read_text("a");
+            Err::<(), _>(ParserResultError::Error((reader.save_cursor(), "error")))
+        });
+
+        let mut reader = ParserInput::new_with_error("abc");
+        let start = reader.save_cursor();
+        let first = parser(&mut reader);
+        reader.restore(start);
+        let second = parser(&mut reader);
+
+        assert_eq!(first, second);
+        assert_eq!(reader.byte_offset(), 1);
+        drop(parser);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_memoize_reported_errors() {
+        let mut calls = 0;
+        let mut statement = memoize(|reader: &mut ParserInput<&'static str>| {
+            calls += 1;
+            recover_until(
+                |reader: &mut ParserInput<'static, &'static str>| {
+                    read_text("ok")(reader).map_err(|_| {
+                        ParserResultError::Error((reader.save_cursor(), "invalid statement"))
+                    })
+                },
+                read_char(';'),
+                |_| "",
+            )(reader)
+        });
+
+        let mut reader = ParserInput::new_with_error("bad;");
+        let start = reader.save_cursor();
+        assert_eq!(statement(&mut reader), Ok(""));
+        assert_eq!(reader.errors().len(), 1);
+
+        reader.rollback_errors(0);
+        reader.restore(start);
+        assert_eq!(statement(&mut reader), Ok(""));
+        assert_eq!(
+            reader.errors(),
+            &[(Cursor::new(0, 0, 1, 1), "invalid statement")]
+        );
+        assert!(reader.is_end());
+        drop(statement);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_memoize_incomplete() {
+        let mut calls = 0;
+        let mut parser = memoize(|reader: &mut ParserInput| {
+            calls += 1;
+            read_text("test")(reader)
+        });
+
+        let mut reader = ParserInput::new("te");
+        reader.set_partial(true);
+        assert_eq!(
+            parser(&mut reader),
+            Err(ParserResultError::Incomplete(Needed::Size(2)))
+        );
+        assert_eq!(
+            parser(&mut reader),
+            Err(ParserResultError::Incomplete(Needed::Size(2)))
+        );
+        drop(parser);
+        assert_eq!(calls, 2);
+    }
+
+    type Input<'a> = ParserInput<'a, &'static str, SeedTable<String, &'static str>>;
+
+    fn list<'a>(reader: &mut Input<'a>) -> ParserResult<String, &'static str> {
+        left_recursive(
+            |context: &mut SeedTable<String, &'static str>| context,
+            alternative((
+                |reader: &mut Input<'a>| {
+                    let (list, _, item) = tuple((list, read_char(','), item))(reader)?;
+                    Ok(format!("({},{})", list, item))
+                },
+                item,
+            )),
+        )(reader)
+    }
+
+    fn item<'a>(reader: &mut Input<'a>) -> ParserResult<String, &'static str> {
+        if reader.read_text("!") {
+            return Err(ParserResultError::Error((reader.save_cursor(), "error")));
+        }
+
+        let item = read_any_of(|_, c| c.is_ascii_alphabetic())(reader)?;
+        Ok(item.to_string())
+    }
+
+    #[test]
+    fn test_left_recursive() {
+        let mut reader = ParserInput::new_with_context_and_error("a,b,c;", SeedTable::new());
+        assert_eq!(list(&mut reader), Ok("((a,b),c)".to_string()));
+        assert_eq!(reader.remaining_content(), ";");
+        assert!(reader.context().is_empty());
+
+        let mut reader = ParserInput::new_with_context_and_error("a,", SeedTable::new());
+        assert_eq!(list(&mut reader), Ok("a".to_string()));
+        assert_eq!(reader.remaining_content(), ",");
+
+        let mut reader = ParserInput::new_with_context_and_error(";", SeedTable::new());
+        assert_eq!(list(&mut reader), Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 0);
+        assert!(reader.context().is_empty());
+
+        let mut reader = ParserInput::new_with_context_and_error("a,b,!", SeedTable::new());
+        let (cursor, error) = list(&mut reader).unwrap_err().unwrap_error();
+        assert_eq!(cursor.byte_offset(), 5);
+        assert_eq!(error, "error");
+        assert!(reader.context().is_empty());
+    }
+}
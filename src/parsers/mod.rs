@@ -1,10 +1,21 @@
+pub use parser::*;
 pub use quantifiers::*;
 
 pub mod branch;
+pub mod bytes;
 pub mod characters;
 pub mod combinator;
+#[cfg(feature = "alloc")]
+pub mod expression;
 pub mod helpers;
+#[cfg(feature = "alloc")]
+pub mod lexer;
+#[cfg(feature = "alloc")]
+pub mod memo;
 pub mod numbers;
+mod parser;
 mod quantifiers;
 pub mod sequence;
+pub mod tokens;
+pub mod trivia;
 pub mod verifiers;
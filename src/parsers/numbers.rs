@@ -1,11 +1,247 @@
+use core::fmt;
+use core::str::FromStr;
+
 use crate::parsers::branch::alternative;
-use crate::parsers::characters::{decimal_digit1, read_any_of, read_char};
+use crate::parsers::characters::{decimal_digit1, read_any_of, read_any_of0, read_char};
 use crate::parsers::combinator::optional;
-use crate::parsers::helpers::{consumed, ensure};
+use crate::parsers::helpers::{consumed, ensure, not_found_restore};
 use crate::parsers::sequence::tuple;
 use crate::parsers::verifiers::text_verifier;
+use crate::result::ParserResultError;
 use crate::sequence::tuple_ignore;
-use crate::{ParserInput, ParserResult};
+use crate::{Cursor, ParserInput, ParserResult, Span};
+
+/// The signs that can precede a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signs {
+    /// No sign is allowed.
+    None,
+    /// Only `-` is allowed.
+    Minus,
+    /// Both `+` and `-` are allowed.
+    PlusMinus,
+}
+
+/// The kind of a [NumberError].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberErrorKind {
+    /// The number is greater than the maximum of the target type.
+    Overflow,
+    /// The number is lower than the minimum of the target type, e.g. a negative number
+    /// for an unsigned type.
+    NegativeOverflow,
+}
+
+/// The error returned by the numeric parsers when the number they read
+/// cannot be represented by the target type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberError<'a> {
+    kind: NumberErrorKind,
+    span: Span<'a>,
+}
+
+impl<'a> NumberError<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new `NumberError`.
+    pub fn new(kind: NumberErrorKind, span: Span<'a>) -> NumberError<'a> {
+        NumberError { kind, span }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The kind of the error.
+    pub fn kind(&self) -> NumberErrorKind {
+        self.kind
+    }
+
+    /// The number that caused the error.
+    pub fn span(&self) -> &Span<'a> {
+        &self.span
+    }
+}
+
+impl<'a> fmt::Display for NumberError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            NumberErrorKind::Overflow => write!(
+                f,
+                "number `{}` is too large to fit in the target type",
+                self.span.content()
+            ),
+            NumberErrorKind::NegativeOverflow => write!(
+                f,
+                "number `{}` is too negative to fit in the target type",
+                self.span.content()
+            ),
+        }
+    }
+}
+
+impl<'a> From<NumberError<'a>> for () {
+    fn from(_: NumberError<'a>) -> Self {}
+}
+
+/// An integer type that can be read by the numeric parsers.
+pub trait Integer: Sized + Copy {
+    /// Builds the number from its `digits` in `radix` ignoring the `_` separators.
+    /// It returns `None` if the number does not fit in the type.
+    fn from_digits(digits: &str, radix: u32, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($type:ty),+ $(,)?) => {
+        $(
+            impl Integer for $type {
+                fn from_digits(digits: &str, radix: u32, negative: bool) -> Option<Self> {
+                    let mut result: $type = 0;
+
+                    for char in digits.chars() {
+                        if char == '_' {
+                            continue;
+                        }
+
+                        let digit = char.to_digit(radix)? as $type;
+                        result = result.checked_mul(radix as $type)?;
+                        result = if negative {
+                            result.checked_sub(digit)?
+                        } else {
+                            result.checked_add(digit)?
+                        };
+                    }
+
+                    Some(result)
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A floating point type that can be read by the numeric parsers.
+pub trait Float: FromStr + Copy {
+    /// Whether the number is infinite or not.
+    fn is_infinite(self) -> bool;
+}
+
+impl Float for f32 {
+    fn is_infinite(self) -> bool {
+        f32::is_infinite(self)
+    }
+}
+
+impl Float for f64 {
+    fn is_infinite(self) -> bool {
+        f64::is_infinite(self)
+    }
+}
+
+/// The configuration of the [integer_literal()] parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerLiteralConfig<'a> {
+    signs: Signs,
+    radix_prefixes: bool,
+    separators: bool,
+    suffixes: &'a [&'a str],
+}
+
+impl<'a> IntegerLiteralConfig<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new configuration that accepts the `0x`, `0o` and `0b` prefixes and the `_`
+    /// separators but neither signs nor suffixes.
+    pub fn new() -> IntegerLiteralConfig<'a> {
+        IntegerLiteralConfig {
+            signs: Signs::None,
+            radix_prefixes: true,
+            separators: true,
+            suffixes: &[],
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// The signs allowed before the literal.
+    pub fn signs(&self) -> Signs {
+        self.signs
+    }
+
+    /// Whether the `0x`, `0o` and `0b` prefixes are allowed or not.
+    pub fn radix_prefixes(&self) -> bool {
+        self.radix_prefixes
+    }
+
+    /// Whether the `_` separators are allowed between the digits or not.
+    pub fn separators(&self) -> bool {
+        self.separators
+    }
+
+    /// The type suffixes allowed after the literal.
+    pub fn suffixes(&self) -> &'a [&'a str] {
+        self.suffixes
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Sets the signs allowed before the literal.
+    pub fn with_signs(mut self, signs: Signs) -> Self {
+        self.signs = signs;
+        self
+    }
+
+    /// Sets whether the `0x`, `0o` and `0b` prefixes are allowed or not.
+    pub fn with_radix_prefixes(mut self, radix_prefixes: bool) -> Self {
+        self.radix_prefixes = radix_prefixes;
+        self
+    }
+
+    /// Sets whether the `_` separators are allowed between the digits or not.
+    pub fn with_separators(mut self, separators: bool) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// Sets the type suffixes allowed after the literal, e.g. `["u8", "i32"]`.
+    /// They are tested in order so longer suffixes must precede their prefixes.
+    pub fn with_suffixes(mut self, suffixes: &'a [&'a str]) -> Self {
+        self.suffixes = suffixes;
+        self
+    }
+}
+
+impl<'a> Default for IntegerLiteralConfig<'a> {
+    fn default() -> Self {
+        IntegerLiteralConfig::new()
+    }
+}
+
+/// An integer literal read by the [integer_literal()] parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntegerLiteral<'a, T> {
+    value: T,
+    radix: u32,
+    suffix: Option<&'a str>,
+}
+
+impl<'a, T: Copy> IntegerLiteral<'a, T> {
+    // GETTERS ----------------------------------------------------------------
+
+    /// The value of the literal.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// The radix in which the literal is written: 2, 8, 10 or 16.
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// The type suffix of the literal if present.
+    pub fn suffix(&self) -> Option<&'a str> {
+        self.suffix
+    }
+}
 
 /// Reads an integer number.
 pub fn read_integer<'a, C, Err>(
@@ -40,6 +276,258 @@ pub fn read_float<'a, C, Err: From<&'static str>>(
     )))(reader)
 }
 
+/// Reads a decimal integer preceded by an optional `+` or `-` sign and converts it to `T`.
+///
+/// If the number does not fit in `T` an error is returned placed at the start of the number.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, ParserResultError};
+/// # use jpar::numbers::{integer, NumberError};
+/// let mut reader = ParserInput::new_with_error::<NumberError>("-128 128");
+///
+/// let result = integer::<i8, _, _>()(&mut reader);
+/// assert_eq!(result, Ok(-128));
+///
+/// reader.read();
+/// let (cursor, error) = integer::<i8, _, _>()(&mut reader).unwrap_err().unwrap_error();
+/// assert_eq!(cursor.byte_offset(), 5);
+/// assert_eq!(error.span().content(), "128");
+/// ```
+pub fn integer<'a, T, C, Err>() -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<T, Err>
+where
+    T: Integer,
+    Err: From<NumberError<'a>>,
+{
+    integer_with_signs(Signs::PlusMinus)
+}
+
+/// Reads a decimal integer without sign and converts it to `T`.
+///
+/// If the number does not fit in `T` an error is returned placed at the start of the number.
+pub fn unsigned<'a, T, C, Err>() -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<T, Err>
+where
+    T: Integer,
+    Err: From<NumberError<'a>>,
+{
+    integer_with_signs(Signs::None)
+}
+
+/// Reads a decimal integer preceded by any of the allowed `signs` and converts it to `T`.
+///
+/// If the number does not fit in `T` an error is returned placed at the start of the number.
+pub fn integer_with_signs<'a, T, C, Err>(
+    signs: Signs,
+) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<T, Err>
+where
+    T: Integer,
+    Err: From<NumberError<'a>>,
+{
+    not_found_restore(move |reader| {
+        let init_cursor = reader.save_cursor();
+        let negative = read_sign(reader, signs)?;
+        let digits = decimal_digit1(reader)?;
+
+        match T::from_digits(digits, 10, negative) {
+            Some(v) => Ok(v),
+            None => Err(number_error(reader, init_cursor, negative)),
+        }
+    })
+}
+
+/// Reads a float number preceded by an optional `+` or `-` sign and converts it to `T`.
+///
+/// If the number is too big to be represented by `T` an error is returned placed at the start of the number.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, ParserResultError};
+/// # use jpar::numbers::{float, NumberError, NumberErrorKind};
+/// let mut reader = ParserInput::new_with_error::<NumberError>("-1.5e3 1e39");
+///
+/// let result = float::<f32, _, _>()(&mut reader);
+/// assert_eq!(result, Ok(-1500.0));
+///
+/// reader.read();
+/// let (_, error) = float::<f32, _, _>()(&mut reader).unwrap_err().unwrap_error();
+/// assert_eq!(error.kind(), NumberErrorKind::Overflow);
+/// ```
+pub fn float<'a, T, C, Err>() -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<T, Err>
+where
+    T: Float,
+    Err: From<NumberError<'a>>,
+{
+    float_with_signs(Signs::PlusMinus)
+}
+
+/// Reads a float number preceded by any of the allowed `signs` and converts it to `T`.
+///
+/// If the number is too big to be represented by `T` an error is returned placed at the start of the number.
+pub fn float_with_signs<'a, T, C, Err>(
+    signs: Signs,
+) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<T, Err>
+where
+    T: Float,
+    Err: From<NumberError<'a>>,
+{
+    not_found_restore(move |reader| {
+        let init_cursor = reader.save_cursor();
+        let negative = read_sign(reader, signs)?;
+
+        alternative((
+            tuple_ignore((
+                decimal_digit1,
+                optional(tuple_ignore((read_char('.'), optional(decimal_digit1)))),
+            )),
+            tuple_ignore((read_char('.'), decimal_digit1)),
+        ))(reader)?;
+        let _ = optional(tuple_ignore((
+            read_any_of(text_verifier("eE")),
+            optional(read_any_of(text_verifier("+-"))),
+            decimal_digit1,
+        )))(reader)?;
+
+        let text = reader.content_to_current(&init_cursor);
+        match text.parse::<T>() {
+            Ok(v) if v.is_infinite() => Err(number_error(reader, init_cursor, negative)),
+            Ok(v) => Ok(v),
+            Err(_) => Err(ParserResultError::NotFound),
+        }
+    })
+}
+
+/// Reads an integer literal as configured by `config`:
+/// an optional sign, an optional radix prefix (`0x`, `0o` or `0b`), the digits optionally
+/// separated by `_` and an optional type suffix.
+///
+/// If the number does not fit in `T` an error is returned placed at the start of the literal.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::ParserInput;
+/// # use jpar::numbers::{integer_literal, IntegerLiteralConfig};
+/// let mut reader = ParserInput::new("0xFF_FFu16");
+/// let config = IntegerLiteralConfig::new().with_suffixes(&["u8", "u16"]);
+///
+/// let literal = integer_literal::<u32, _, _>(config)(&mut reader).unwrap();
+/// assert_eq!(literal.value(), 0xFFFF);
+/// assert_eq!(literal.radix(), 16);
+/// assert_eq!(literal.suffix(), Some("u16"));
+/// ```
+pub fn integer_literal<'a, T, C, Err>(
+    config: IntegerLiteralConfig<'a>,
+) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<IntegerLiteral<'a, T>, Err>
+where
+    T: Integer,
+    Err: From<NumberError<'a>>,
+{
+    not_found_restore(move |reader| {
+        let init_cursor = reader.save_cursor();
+        let negative = read_sign(reader, config.signs)?;
+        let digits_cursor = reader.save_cursor();
+
+        let mut radix = 10;
+        let mut digits = None;
+
+        if config.radix_prefixes {
+            let prefixed_radix = match reader.remaining_content().as_bytes() {
+                [b'0', b'x', ..] | [b'0', b'X', ..] => Some(16),
+                [b'0', b'o', ..] | [b'0', b'O', ..] => Some(8),
+                [b'0', b'b', ..] | [b'0', b'B', ..] => Some(2),
+                _ => None,
+            };
+
+            if let Some(prefixed_radix) = prefixed_radix {
+                reader.read_quantified(2);
+
+                match read_literal_digits(reader, prefixed_radix, config.separators, true) {
+                    Ok(v) => {
+                        radix = prefixed_radix;
+                        digits = Some(v);
+                    }
+                    // Without digits the prefix is not a prefix, e.g. a decimal 0 followed by `x`.
+                    Err(ParserResultError::NotFound) => reader.restore(digits_cursor),
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        let digits = match digits {
+            Some(v) => v,
+            None => read_literal_digits(reader, 10, config.separators, false)?,
+        };
+
+        let value = match T::from_digits(digits, radix, negative) {
+            Some(v) => v,
+            None => return Err(number_error(reader, init_cursor, negative)),
+        };
+
+        let suffix = config
+            .suffixes
+            .iter()
+            .find(|suffix| reader.read_text(suffix))
+            .copied();
+
+        Ok(IntegerLiteral {
+            value,
+            radix,
+            suffix,
+        })
+    })
+}
+
+/// Reads any of the allowed `signs` returning whether the number is negative or not.
+fn read_sign<C, Err>(reader: &mut ParserInput<Err, C>, signs: Signs) -> ParserResult<bool, Err> {
+    let sign = match signs {
+        Signs::None => None,
+        Signs::Minus => optional(read_char('-'))(reader)?,
+        Signs::PlusMinus => optional(read_any_of(text_verifier("+-")))(reader)?,
+    };
+
+    Ok(sign == Some('-'))
+}
+
+/// Reads the digits of a literal in `radix` that can contain `_` separators if `separators` is set.
+/// They must start with a digit unless `leading_separator` is set, and contain at least one.
+fn read_literal_digits<'a, C, Err>(
+    reader: &mut ParserInput<'a, Err, C>,
+    radix: u32,
+    separators: bool,
+    leading_separator: bool,
+) -> ParserResult<&'a str, Err> {
+    let digits = read_any_of0(|i, c| {
+        c.is_digit(radix) || (separators && c == '_' && (i > 0 || leading_separator))
+    })(reader)?;
+
+    if digits.chars().any(|c| c != '_') {
+        Ok(digits)
+    } else {
+        Err(ParserResultError::NotFound)
+    }
+}
+
+/// Builds the error of a number that starts at `cursor` and finishes at the current position.
+fn number_error<'a, C, Err>(
+    reader: &ParserInput<'a, Err, C>,
+    cursor: Cursor,
+    negative: bool,
+) -> ParserResultError<Err>
+where
+    Err: From<NumberError<'a>>,
+{
+    let kind = if negative {
+        NumberErrorKind::NegativeOverflow
+    } else {
+        NumberErrorKind::Overflow
+    };
+    let span = reader.substring_to_current(&cursor);
+
+    ParserResultError::Error((cursor, NumberError::new(kind, span).into()))
+}
+
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
@@ -103,4 +591,170 @@ mod test {
         let result = read_float(&mut reader);
         assert_eq!(result, Err(ParserResultError::NotFound));
     }
+
+    #[test]
+    fn test_integer() {
+        let mut reader = ParserInput::new("-9223372036854775808 +12 -0");
+
+        let result = integer::<i64, _, _>()(&mut reader);
+        assert_eq!(result, Ok(i64::MIN));
+
+        reader.read();
+        let result = integer::<u8, _, _>()(&mut reader);
+        assert_eq!(result, Ok(12));
+
+        reader.read();
+        let result = integer::<u8, _, _>()(&mut reader);
+        assert_eq!(result, Ok(0));
+
+        let mut reader = ParserInput::new("+a");
+        let result = integer::<i32, _, _>()(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 0);
+    }
+
+    #[test]
+    fn test_integer_errors() {
+        let mut reader = ParserInput::new_with_error::<NumberError>("x 256");
+        reader.read();
+        reader.read();
+
+        let (cursor, error) = integer::<u8, _, _>()(&mut reader)
+            .unwrap_err()
+            .unwrap_error();
+        assert_eq!(cursor.byte_offset(), 2);
+        assert_eq!(error.kind(), NumberErrorKind::Overflow);
+        assert_eq!(error.span().content(), "256");
+        assert_eq!(
+            error.to_string(),
+            "number `256` is too large to fit in the target type"
+        );
+
+        let mut reader = ParserInput::new_with_error::<NumberError>("-1");
+        let (_, error) = integer::<u32, _, _>()(&mut reader)
+            .unwrap_err()
+            .unwrap_error();
+        assert_eq!(error.kind(), NumberErrorKind::NegativeOverflow);
+        assert_eq!(error.span().content(), "-1");
+        assert_eq!(
+            error.to_string(),
+            "number `-1` is too negative to fit in the target type"
+        );
+
+        let mut reader = ParserInput::new_with_error::<NumberError>("-129");
+        let (_, error) = integer::<i8, _, _>()(&mut reader)
+            .unwrap_err()
+            .unwrap_error();
+        assert_eq!(error.kind(), NumberErrorKind::NegativeOverflow);
+    }
+
+    #[test]
+    fn test_integer_signs() {
+        let mut reader = ParserInput::new("-5");
+        let result = unsigned::<u32, _, _>()(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+
+        let result = integer_with_signs::<i32, _, _>(Signs::Minus)(&mut reader);
+        assert_eq!(result, Ok(-5));
+
+        let mut reader = ParserInput::new("+5");
+        let result = integer_with_signs::<i32, _, _>(Signs::Minus)(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+    }
+
+    #[test]
+    fn test_float() {
+        let mut reader = ParserInput::new("-.5 1. 2.5E-1 3e");
+
+        let result = float::<f64, _, _>()(&mut reader);
+        assert_eq!(result, Ok(-0.5));
+
+        reader.read();
+        let result = float::<f64, _, _>()(&mut reader);
+        assert_eq!(result, Ok(1.0));
+
+        reader.read();
+        let result = float::<f32, _, _>()(&mut reader);
+        assert_eq!(result, Ok(0.25));
+
+        reader.read();
+        let result = float::<f64, _, _>()(&mut reader);
+        assert_eq!(result, Ok(3.0));
+        assert_eq!(reader.remaining_content(), "e");
+
+        let mut reader = ParserInput::new("+5");
+        let result = float_with_signs::<f64, _, _>(Signs::None)(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+
+        let mut reader = ParserInput::new_with_error::<NumberError>("-1e400");
+        let (cursor, error) = float::<f64, _, _>()(&mut reader)
+            .unwrap_err()
+            .unwrap_error();
+        assert_eq!(cursor.byte_offset(), 0);
+        assert_eq!(error.kind(), NumberErrorKind::NegativeOverflow);
+        assert_eq!(error.span().content(), "-1e400");
+    }
+
+    #[test]
+    fn test_integer_literal() {
+        let config = IntegerLiteralConfig::new();
+        let mut reader = ParserInput::new("0x1F 0o17 0b1_01 1_000 0xg 0b_1");
+
+        let tests: [(u64, u32, usize); 6] = [
+            (0x1F, 16, 4),
+            (0o17, 8, 9),
+            (0b101, 2, 16),
+            (1000, 10, 22),
+            (0, 10, 24),
+            (0b1, 2, 31),
+        ];
+        for (value, radix, offset) in tests.iter() {
+            let literal = integer_literal::<u64, _, _>(config)(&mut reader).unwrap();
+            assert_eq!(literal.value(), *value);
+            assert_eq!(literal.radix(), *radix);
+            assert_eq!(literal.suffix(), None);
+            assert_eq!(reader.byte_offset(), *offset);
+
+            if !reader.is_end() {
+                let _ = read_any_of0(|_, c| !c.is_ascii_whitespace())(&mut reader);
+                reader.read();
+            }
+        }
+
+        let mut reader = ParserInput::new("_1");
+        let result = integer_literal::<u64, _, _>(config)(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+    }
+
+    #[test]
+    fn test_integer_literal_config() {
+        let config = IntegerLiteralConfig::new()
+            .with_signs(Signs::Minus)
+            .with_suffixes(&["i8", "i16", "u8"]);
+        let mut reader = ParserInput::new("-0x80i8");
+
+        let literal = integer_literal::<i8, _, _>(config)(&mut reader).unwrap();
+        assert_eq!(literal.value(), -128);
+        assert_eq!(literal.radix(), 16);
+        assert_eq!(literal.suffix(), Some("i8"));
+        assert!(reader.is_end());
+
+        let config = IntegerLiteralConfig::new()
+            .with_radix_prefixes(false)
+            .with_separators(false);
+        let mut reader = ParserInput::new("0x1_0");
+
+        let literal = integer_literal::<i8, _, _>(config)(&mut reader).unwrap();
+        assert_eq!(literal.value(), 0);
+        assert_eq!(literal.radix(), 10);
+        assert_eq!(reader.remaining_content(), "x1_0");
+
+        let mut reader = ParserInput::new_with_error::<NumberError>("0x1_00u8");
+        let (cursor, error) = integer_literal::<u8, _, _>(IntegerLiteralConfig::new())(&mut reader)
+            .unwrap_err()
+            .unwrap_error();
+        assert_eq!(cursor.byte_offset(), 0);
+        assert_eq!(error.kind(), NumberErrorKind::Overflow);
+        assert_eq!(error.span().content(), "0x1_00");
+    }
 }
@@ -0,0 +1,400 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::parsers::combinator::{not_consume, optional, optional_default, verify};
+use crate::parsers::helpers::{
+    and_then, consumed, ensure, ignore_result, map_result, not_found_restore, recover,
+};
+use crate::parsers::sequence::{delimited, preceded, repeat_and_count, terminated};
+use crate::parsers::Quantifier;
+use crate::result::ParserResult;
+use crate::{Cursor, InputContent, ParserInput};
+
+/// A parser that reads from a `ParserInput`.
+///
+/// This trait is implemented for every `FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>`,
+/// so any existing parser can use the chaining methods, and it can also be implemented by custom
+/// types to build parsers that carry their own state.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{Parser, ParserInput};
+/// # use jpar::characters::{decimal_digit1, read_char};
+/// let mut reader = ParserInput::new("(123)");
+/// let mut parser = decimal_digit1
+///     .map(|_, digits| digits.len())
+///     .delimited_by(read_char('('), read_char(')'));
+///
+/// assert_eq!(parser.parse(&mut reader), Ok(3));
+/// ```
+pub trait Parser<'a, Err = (), C = (), I: InputContent + ?Sized = str> {
+    /// The value returned by the parser when it succeeds.
+    type Output;
+
+    /// Applies the parser over `reader`.
+    fn parse(&mut self, reader: &mut ParserInput<'a, Err, C, I>)
+        -> ParserResult<Self::Output, Err>;
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Borrows the parser as a closure, so it can be passed to other combinators without moving it.
+    fn by_ref(
+        &mut self,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err> + '_
+    where
+        Self: Sized,
+    {
+        move |reader| self.parse(reader)
+    }
+
+    /// Converts the parser into a closure, so it can be passed to the combinators that
+    /// require an `FnMut`, like `alternative` or `tuple`.
+    fn into_fn(
+        mut self,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+    {
+        move |reader| self.parse(reader)
+    }
+
+    /// Maps the result of the parser into a new value.
+    /// See [map_result()](crate::helpers::map_result).
+    fn map<M, Rf>(
+        self,
+        mapper: M,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rf, Err>
+    where
+        Self: Sized,
+        M: FnMut(&mut ParserInput<'a, Err, C, I>, Self::Output) -> Rf,
+    {
+        map_result(self.into_fn(), mapper)
+    }
+
+    /// Maps the result of the parser into a new `ParserResult`.
+    /// See [and_then()](crate::helpers::and_then).
+    fn and_then<M, Rf>(
+        self,
+        mapper: M,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rf, Err>
+    where
+        Self: Sized,
+        M: FnMut(&mut ParserInput<'a, Err, C, I>, Self::Output) -> ParserResult<Rf, Err>,
+    {
+        and_then(self.into_fn(), mapper)
+    }
+
+    /// Returns the result of the parser only if it satisfies `verifier`.
+    /// See [verify()](crate::combinator::verify).
+    fn verify<V>(
+        self,
+        verifier: V,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+        V: FnMut(&mut ParserInput<'a, Err, C, I>, &Self::Output) -> bool,
+    {
+        verify(self.into_fn(), verifier)
+    }
+
+    /// Returns `None` when the parser is not found.
+    /// See [optional()](crate::combinator::optional).
+    fn optional(
+        self,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Option<Self::Output>, Err>
+    where
+        Self: Sized,
+    {
+        optional(self.into_fn())
+    }
+
+    /// Returns the default value when the parser is not found.
+    /// See [optional_default()](crate::combinator::optional_default).
+    fn optional_default(
+        self,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+        Self::Output: Default,
+    {
+        optional_default(self.into_fn())
+    }
+
+    /// Executes the parser without consuming any character.
+    /// See [not_consume()](crate::combinator::not_consume).
+    fn not_consume(
+        self,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+    {
+        not_consume(self.into_fn())
+    }
+
+    /// Repeats the parser a quantified number of times.
+    /// See [repeat()](crate::sequence::repeat).
+    #[cfg(feature = "alloc")]
+    fn repeat(
+        self,
+        quantifier: impl Into<Quantifier>,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Vec<Self::Output>, Err>
+    where
+        Self: Sized,
+    {
+        crate::parsers::sequence::repeat(quantifier, self.into_fn())
+    }
+
+    /// Repeats the parser a quantified number of times and returns the number of repetitions.
+    /// See [repeat_and_count()](crate::sequence::repeat_and_count).
+    fn repeat_and_count(
+        self,
+        quantifier: impl Into<Quantifier>,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<usize, Err>
+    where
+        Self: Sized,
+    {
+        repeat_and_count(quantifier, self.into_fn())
+    }
+
+    /// Surrounds the parser by `prefix` and `postfix` discarding their results.
+    /// See [delimited()](crate::sequence::delimited).
+    fn delimited_by<Pre, Pos>(
+        self,
+        prefix: Pre,
+        postfix: Pos,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+        Pre: Parser<'a, Err, C, I>,
+        Pos: Parser<'a, Err, C, I>,
+    {
+        delimited(prefix.into_fn(), self.into_fn(), postfix.into_fn())
+    }
+
+    /// Precedes the parser by `prefix` discarding its result.
+    /// See [preceded()](crate::sequence::preceded).
+    fn preceded_by<Pre>(
+        self,
+        prefix: Pre,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+        Pre: Parser<'a, Err, C, I>,
+    {
+        preceded(prefix.into_fn(), self.into_fn())
+    }
+
+    /// Terminates the parser by `postfix` discarding its result.
+    /// See [terminated()](crate::sequence::terminated).
+    fn terminated_by<Pos>(
+        self,
+        postfix: Pos,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+        Pos: Parser<'a, Err, C, I>,
+    {
+        terminated(self.into_fn(), postfix.into_fn())
+    }
+
+    /// Discards the result of the parser and returns the consumed content instead.
+    /// See [consumed()](crate::helpers::consumed).
+    fn consumed(self) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<&'a I, Err>
+    where
+        Self: Sized,
+        I: InputContent + 'a,
+    {
+        consumed(self.into_fn())
+    }
+
+    /// Discards the result of the parser.
+    /// See [ignore_result()](crate::helpers::ignore_result).
+    fn ignore_result(self) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err>
+    where
+        Self: Sized,
+    {
+        ignore_result(self.into_fn())
+    }
+
+    /// Restores the reader when the parser is not found.
+    /// See [not_found_restore()](crate::helpers::not_found_restore).
+    fn not_found_restore(
+        self,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+    {
+        not_found_restore(self.into_fn())
+    }
+
+    /// Ensures the parser always succeeds or returns the error built by `error_fn`.
+    /// See [ensure()](crate::helpers::ensure).
+    fn ensure<Efn>(
+        self,
+        error_fn: Efn,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+        Efn: FnMut(&mut ParserInput<'a, Err, C, I>) -> Err,
+    {
+        ensure(self.into_fn(), error_fn)
+    }
+
+    /// Allows to recover in case the parser returns an error.
+    /// See [recover()](crate::helpers::recover).
+    fn recover<Rfn>(
+        self,
+        recover_fn: Rfn,
+    ) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Self::Output, Err>
+    where
+        Self: Sized,
+        Rfn: FnMut(&mut ParserInput<'a, Err, C, I>, Cursor, Err) -> ParserResult<Self::Output, Err>,
+    {
+        recover(self.into_fn(), recover_fn)
+    }
+}
+
+impl<'a, Err, C, I: InputContent + ?Sized, R, F> Parser<'a, Err, C, I> for F
+where
+    F: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    type Output = R;
+
+    #[inline]
+    fn parse(&mut self, reader: &mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err> {
+        self(reader)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::parsers::branch::alternative;
+    use crate::parsers::characters::{ascii_alpha1, decimal_digit1, read_char, read_text};
+    use crate::result::ParserResultError;
+
+    use super::*;
+
+    /// A parser that counts how many times it has matched.
+    struct Counter {
+        count: usize,
+    }
+
+    impl<'a, Err, C> Parser<'a, Err, C> for Counter {
+        type Output = usize;
+
+        fn parse(&mut self, reader: &mut ParserInput<'a, Err, C>) -> ParserResult<usize, Err> {
+            if reader.read_text("a") {
+                self.count += 1;
+                Ok(self.count)
+            } else {
+                Err(ParserResultError::NotFound)
+            }
+        }
+    }
+
+    #[test]
+    fn test_closure_parser() {
+        let mut reader = ParserInput::new("This is a test");
+        let result = read_text("This").parse(&mut reader);
+        assert_eq!(result, Ok("This"));
+
+        let result = ascii_alpha1.parse(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+    }
+
+    #[test]
+    fn test_custom_parser() {
+        let mut reader = ParserInput::new("aaab");
+        let mut counter = Counter { count: 0 };
+
+        assert_eq!(counter.parse(&mut reader), Ok(1));
+        assert_eq!(
+            counter.by_ref().repeat_and_count(..).parse(&mut reader),
+            Ok(2)
+        );
+        assert_eq!(counter.count, 3);
+
+        let result = alternative((counter.into_fn(), read_char('b').map(|_, _| 0)))(&mut reader);
+        assert_eq!(result, Ok(0));
+    }
+
+    #[test]
+    fn test_chaining() {
+        let mut reader = ParserInput::new("(123)(a)");
+        let mut parser = decimal_digit1
+            .map(|_, v| v.len())
+            .delimited_by(read_char('('), read_char(')'));
+
+        let result = parser.parse(&mut reader);
+        assert_eq!(result, Ok(3));
+
+        let result = parser.parse(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 5);
+
+        let mut parser = ascii_alpha1
+            .optional()
+            .preceded_by(read_char('('))
+            .terminated_by(read_char(')'))
+            .consumed();
+        let result = parser.parse(&mut reader);
+        assert_eq!(result, Ok("(a)"));
+    }
+
+    #[test]
+    fn test_and_then_verify() {
+        let mut reader = ParserInput::new("123 abc");
+        let mut parser = decimal_digit1
+            .and_then(|_, v| Ok(v.len()))
+            .verify(|_, v| *v == 3);
+
+        let result = parser.parse(&mut reader);
+        assert_eq!(result, Ok(3));
+
+        let result = parser.parse(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+    }
+
+    #[test]
+    fn test_ensure_recover() {
+        let mut reader = ParserInput::new_with_error::<&str>("abc");
+        let mut parser = decimal_digit1.ensure(|_| "digits expected");
+
+        let result = parser.parse(&mut reader);
+        assert_eq!(result.unwrap_err().unwrap_error().1, "digits expected");
+
+        let mut parser = decimal_digit1
+            .ensure(|_| "digits expected")
+            .recover(|_, _, _| Ok("0"));
+        let result = parser.parse(&mut reader);
+        assert_eq!(result, Ok("0"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod test_alloc {
+    use crate::parsers::characters::{ascii_alpha, read_char};
+    use crate::result::ParserResultError;
+
+    use super::*;
+
+    #[test]
+    fn test_repeat() {
+        let mut reader = ParserInput::new("a,b,c");
+        let mut parser = ascii_alpha
+            .terminated_by(read_char(',').optional())
+            .repeat(2..);
+
+        let result = parser.parse(&mut reader);
+        assert_eq!(result, Ok(vec!['a', 'b', 'c']));
+
+        let result = parser.parse(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::{RangeFrom, RangeFull, RangeInclusive, RangeToInclusive};
+use core::ops::{RangeFrom, RangeFull, RangeInclusive, RangeToInclusive};
 
 /// A quantifier that specify a range of repetitions with both ends included.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
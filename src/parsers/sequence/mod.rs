@@ -4,7 +4,7 @@ pub use tuples_ignore::*;
 
 use crate::parsers::helpers::not_found_restore;
 use crate::result::ParserResult;
-use crate::ParserInput;
+use crate::{InputContent, ParserInput};
 
 mod repeats;
 mod tuples;
@@ -13,15 +13,15 @@ mod tuples_ignore;
 /// Matches an object from the first parser and discards it,
 /// then gets an object from the second parser, and finally
 /// matches an object from the third parser and discards it.
-pub fn delimited<'a, Pre, Con, Pos, C, RPre, R, RPos, Err>(
+pub fn delimited<'a, Pre, Con, Pos, C, RPre, R, RPos, Err, I: InputContent + ?Sized>(
     mut prefix: Pre,
     mut content: Con,
     mut postfix: Pos,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    Pre: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RPre, Err>,
-    Con: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    Pos: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RPos, Err>,
+    Pre: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RPre, Err>,
+    Con: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    Pos: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RPos, Err>,
 {
     not_found_restore(move |reader| {
         let _ = prefix(reader)?;
@@ -33,13 +33,13 @@ where
 }
 
 /// Matches an object from the first parser and discards it, then gets an object from the second parser.
-pub fn preceded<'a, Pre, Con, C, RPre, R, Err>(
+pub fn preceded<'a, Pre, Con, C, RPre, R, Err, I: InputContent + ?Sized>(
     mut prefix: Pre,
     mut content: Con,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    Pre: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RPre, Err>,
-    Con: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    Pre: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RPre, Err>,
+    Con: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     not_found_restore(move |reader| {
         let _ = prefix(reader)?;
@@ -48,13 +48,13 @@ where
 }
 
 /// Gets an object from the first parser, then matches an object from the second parser and discards it.
-pub fn terminated<'a, Con, Pos, C, R, RPos, Err>(
+pub fn terminated<'a, Con, Pos, C, R, RPos, Err, I: InputContent + ?Sized>(
     mut content: Con,
     mut postfix: Pos,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    Con: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    Pos: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RPos, Err>,
+    Con: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    Pos: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RPos, Err>,
 {
     not_found_restore(move |reader| {
         let result = content(reader)?;
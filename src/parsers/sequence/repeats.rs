@@ -1,16 +1,23 @@
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::parsers::helpers::not_found_restore;
 use crate::parsers::Quantifier;
 use crate::result::{ParserResult, ParserResultError};
-use crate::ParserInput;
+use crate::{Cursor, InputContent, ParserInput};
 
 /// Repeats a parser a quantified number of times.
 #[cfg(feature = "alloc")]
-pub fn repeat<'a, P, C, R, Err>(
+pub fn repeat<'a, P, C, R, Err, I: InputContent + ?Sized>(
     quantifier: impl Into<Quantifier>,
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Vec<R>, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Vec<R>, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     let quantifier = quantifier.into();
 
@@ -35,14 +42,14 @@ where
 
 /// Alternates between two parsers to produce a list of elements.
 #[cfg(feature = "alloc")]
-pub fn repeat_separated<'a, P, S, C, R, RSep, Err>(
+pub fn repeat_separated<'a, P, S, C, R, RSep, Err, I: InputContent + ?Sized>(
     quantifier: impl Into<Quantifier>,
     mut parser: P,
     mut separator: S,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Vec<R>, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Vec<R>, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    S: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RSep, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>,
 {
     let quantifier = quantifier.into();
 
@@ -77,11 +84,188 @@ where
     })
 }
 
+/// Repeats a parser a quantified number of times collecting the results into
+/// any container, e.g. a `String`, a `HashMap` or a `BTreeSet`.
+///
+/// # Example
+///
+/// ```
+/// # use std::collections::BTreeSet;
+/// # use jpar::ParserInput;
+/// # use jpar::characters::ascii_alpha;
+/// # use jpar::sequence::repeat_collect;
+/// let mut reader = ParserInput::new("abba");
+/// let result: Result<BTreeSet<char>, _> = repeat_collect(.., ascii_alpha)(&mut reader);
+/// assert_eq!(result, Ok(vec!['a', 'b'].into_iter().collect()));
+/// ```
+pub fn repeat_collect<'a, Coll, P, C, R, Err, I: InputContent + ?Sized>(
+    quantifier: impl Into<Quantifier>,
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Coll, Err>
+where
+    Coll: Default + Extend<R>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    let quantifier = quantifier.into();
+
+    not_found_restore(move |reader| {
+        let mut result = Coll::default();
+        let mut iteration = 0;
+
+        while !quantifier.is_finished(iteration) {
+            result.extend(Some(match parser(reader) {
+                Ok(v) => v,
+                Err(ParserResultError::NotFound) => break,
+                Err(e) => return Err(e),
+            }));
+            iteration += 1;
+        }
+
+        if quantifier.contains(iteration) {
+            Ok(result)
+        } else {
+            Err(ParserResultError::NotFound)
+        }
+    })
+}
+
+/// Alternates between two parsers collecting the elements into any container.
+/// See [repeat_collect()].
+pub fn repeat_separated_collect<'a, Coll, P, S, C, R, RSep, Err, I: InputContent + ?Sized>(
+    quantifier: impl Into<Quantifier>,
+    mut parser: P,
+    mut separator: S,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Coll, Err>
+where
+    Coll: Default + Extend<R>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>,
+{
+    let quantifier = quantifier.into();
+
+    not_found_restore(move |reader| {
+        let mut result = Coll::default();
+        let mut iteration = 0;
+
+        while !quantifier.is_finished(iteration) {
+            let init_loop_cursor = reader.save_cursor();
+            if iteration != 0 {
+                match separator(reader) {
+                    Ok(_) => {}
+                    Err(ParserResultError::NotFound) => break,
+                    Err(e) => return Err(e),
+                }
+            }
+
+            result.extend(Some(match parser(reader) {
+                Ok(v) => v,
+                Err(ParserResultError::NotFound) => {
+                    reader.restore(init_loop_cursor);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }));
+            iteration += 1;
+        }
+
+        if quantifier.contains(iteration) {
+            Ok(result)
+        } else {
+            Err(ParserResultError::NotFound)
+        }
+    })
+}
+
+/// Repeats a parser a quantified number of times concatenating the texts it returns.
+///
+/// When all the texts are adjacent fragments of the input the result borrows the
+/// whole fragment, otherwise it is an owned `String`.
+///
+/// # Example
+///
+/// ```
+/// # use std::borrow::Cow;
+/// # use jpar::ParserInput;
+/// # use jpar::characters::{ascii_alpha1, read_char};
+/// # use jpar::sequence::{repeat_concat, terminated};
+/// let mut reader = ParserInput::new("abc");
+/// let result = repeat_concat(.., ascii_alpha1)(&mut reader);
+/// assert_eq!(result, Ok(Cow::Borrowed("abc")));
+///
+/// let mut reader = ParserInput::new("a-b-c-");
+/// let result = repeat_concat(.., terminated(ascii_alpha1, read_char('-')))(&mut reader);
+/// assert_eq!(result, Ok(Cow::Owned("abc".to_string())));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn repeat_concat<'a, P, C, Err>(
+    quantifier: impl Into<Quantifier>,
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Cow<'a, str>, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err>,
+{
+    let quantifier = quantifier.into();
+
+    not_found_restore(move |reader| {
+        let content: &'a str = reader.content();
+        let content_start = content.as_ptr() as usize;
+
+        // The offsets of the borrowed fragment, or the owned text once the pieces are not adjacent.
+        let mut borrowed: Option<(usize, usize)> = None;
+        let mut owned: Option<String> = None;
+        let mut iteration = 0;
+
+        while !quantifier.is_finished(iteration) {
+            let piece = match parser(reader) {
+                Ok(v) => v,
+                Err(ParserResultError::NotFound) => break,
+                Err(e) => return Err(e),
+            };
+            iteration += 1;
+
+            if piece.is_empty() {
+                continue;
+            }
+
+            if let Some(owned) = &mut owned {
+                owned.push_str(piece);
+                continue;
+            }
+
+            let from = (piece.as_ptr() as usize).wrapping_sub(content_start);
+            let is_inside = from < content.len() && piece.len() <= content.len() - from;
+            borrowed = match borrowed {
+                None if is_inside => Some((from, from + piece.len())),
+                Some((start, end)) if is_inside && from == end => Some((start, end + piece.len())),
+                _ => {
+                    let mut text = match borrowed {
+                        Some((start, end)) => content[start..end].to_string(),
+                        None => String::new(),
+                    };
+                    text.push_str(piece);
+                    owned = Some(text);
+                    None
+                }
+            };
+        }
+
+        if !quantifier.contains(iteration) {
+            return Err(ParserResultError::NotFound);
+        }
+
+        Ok(match (owned, borrowed) {
+            (Some(owned), _) => Cow::Owned(owned),
+            (None, Some((start, end))) => Cow::Borrowed(&content[start..end]),
+            (None, None) => Cow::Borrowed(""),
+        })
+    })
+}
+
 /// Repeats a parser a quantified number of times and returns the number of repetitions.
-pub fn repeat_and_count<'a, C, R, Err>(
+pub fn repeat_and_count<'a, C, R, Err, I: InputContent + ?Sized>(
     quantifier: impl Into<Quantifier>,
-    mut parser: impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<usize, Err> {
+    mut parser: impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<usize, Err> {
     let quantifier = quantifier.into();
 
     not_found_restore(move |reader| {
@@ -105,14 +289,14 @@ pub fn repeat_and_count<'a, C, R, Err>(
 }
 
 /// Alternates between two parsers and returns the number of repetitions.
-pub fn repeat_and_count_separated<'a, P, S, C, R, RSep, Err>(
+pub fn repeat_and_count_separated<'a, P, S, C, R, RSep, Err, I: InputContent + ?Sized>(
     quantifier: impl Into<Quantifier>,
     mut parser: P,
     mut separator: S,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<usize, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<usize, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-    S: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RSep, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+    S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>,
 {
     let quantifier = quantifier.into();
 
@@ -149,10 +333,10 @@ where
 }
 
 /// Repeats a parser to fill a slice.
-pub fn repeat_to_fill<'a, C, R, Err>(
+pub fn repeat_to_fill<'a, C, R, Err, I: InputContent + ?Sized>(
     buffer: &'a mut [R],
-    mut parser: impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<(), Err> {
+    mut parser: impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err> {
     not_found_restore(move |reader| {
         for item in buffer.iter_mut() {
             match parser(reader) {
@@ -168,14 +352,14 @@ pub fn repeat_to_fill<'a, C, R, Err>(
 }
 
 /// Applies a parser until it fails and accumulates the results using a given function and initial value.
-pub fn repeat_and_fold<'a, P, F, C, R: Clone, Rp, Err>(
+pub fn repeat_and_fold<'a, P, F, C, R: Clone, Rp, Err, I: InputContent + ?Sized>(
     quantifier: impl Into<Quantifier>,
     init: R,
     fold: F,
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Rp, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Rp, Err>,
     F: Fn(R, Rp) -> R,
 {
     let quantifier = quantifier.into();
@@ -204,13 +388,13 @@ where
 
 /// Gets a number from the first parser, then applies the second parser that many times.
 #[cfg(feature = "alloc")]
-pub fn count_and_repeat<'a, Rep, P, C, R, Err>(
+pub fn count_and_repeat<'a, Rep, P, C, R, Err, I: InputContent + ?Sized>(
     mut repetitions: Rep,
     mut parser: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<Vec<R>, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<Vec<R>, Err>
 where
-    Rep: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<usize, Err>,
-    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+    Rep: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<usize, Err>,
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
 {
     not_found_restore(move |reader| {
         let repetitions = repetitions(reader)?;
@@ -224,6 +408,102 @@ where
     })
 }
 
+/// Repeats a parser a quantified number of times lazily, yielding each result
+/// through an iterator that borrows `reader`.
+///
+/// If the repetition finishes without reaching the minimum of `quantifier`,
+/// the iterator yields `NotFound` after restoring `reader` to its initial position.
+/// After an error, or any other `Err`, the iterator finishes.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::ParserInput;
+/// # use jpar::characters::{ascii_alpha1, read_char};
+/// # use jpar::sequence::{repeat_iter, terminated};
+/// let mut reader = ParserInput::new("a,bc,d,!");
+///
+/// let mut length = 0;
+/// for word in repeat_iter(2.., terminated(ascii_alpha1, read_char(',')), &mut reader) {
+///     length += word.unwrap().len();
+/// }
+///
+/// assert_eq!(length, 4);
+/// assert_eq!(reader.remaining_content(), "!");
+/// ```
+pub fn repeat_iter<'a, 'r, P, C, R, Err, I: ?Sized>(
+    quantifier: impl Into<Quantifier>,
+    parser: P,
+    reader: &'r mut ParserInput<'a, Err, C, I>,
+) -> RepeatIter<'a, 'r, P, Err, C, I>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    RepeatIter {
+        quantifier: quantifier.into(),
+        parser,
+        init_cursor: reader.save_cursor(),
+        reader,
+        iteration: 0,
+        finished: false,
+    }
+}
+
+/// The iterator returned by [repeat_iter()].
+pub struct RepeatIter<'a, 'r, P, Err, C, I: ?Sized> {
+    quantifier: Quantifier,
+    parser: P,
+    reader: &'r mut ParserInput<'a, Err, C, I>,
+    init_cursor: Cursor,
+    iteration: usize,
+    finished: bool,
+}
+
+impl<'a, 'r, P, Err, C, I: ?Sized> RepeatIter<'a, 'r, P, Err, C, I> {
+    // GETTERS ----------------------------------------------------------------
+
+    /// The number of results yielded so far.
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+}
+
+impl<'a, 'r, P, C, R, Err, I: InputContent + ?Sized> Iterator for RepeatIter<'a, 'r, P, Err, C, I>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>,
+{
+    type Item = ParserResult<R, Err>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if !self.quantifier.is_finished(self.iteration) {
+            match (self.parser)(self.reader) {
+                Ok(v) => {
+                    self.iteration += 1;
+                    return Some(Ok(v));
+                }
+                Err(ParserResultError::NotFound) => {}
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        self.finished = true;
+
+        if self.quantifier.contains(self.iteration) {
+            None
+        } else {
+            self.reader.restore(self.init_cursor.clone());
+            Some(Err(ParserResultError::NotFound))
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
@@ -310,13 +590,63 @@ mod test {
 
         assert_eq!(result, Ok("This".to_string()));
     }
+
+    #[test]
+    fn test_repeat_collect() {
+        let mut reader = ParserInput::new("Test Test Tes");
+        let result: ParserResult<String> = repeat_collect(.., read_text("Test "))(&mut reader);
+        assert_eq!(result, Ok("Test Test ".to_string()));
+        assert_eq!(reader.remaining_content(), "Tes");
+
+        let mut reader = ParserInput::new("Test Test Tes");
+        let result: ParserResult<String> = repeat_collect(3, read_text("Test "))(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+        assert_eq!(reader.byte_offset(), 0);
+    }
+
+    #[test]
+    fn test_repeat_separated_collect() {
+        let mut reader = ParserInput::new("a,b,a,");
+        let result: ParserResult<std::collections::BTreeSet<char>> =
+            repeat_separated_collect(.., ascii_alpha, read_text(","))(&mut reader);
+        assert_eq!(result, Ok(['a', 'b'].iter().copied().collect()));
+        assert_eq!(reader.remaining_content(), ",");
+    }
+
+    #[test]
+    fn test_repeat_iter() {
+        let mut reader = ParserInput::new("Test Test Tes");
+        let mut iter = repeat_iter(1..=3, read_text("Test "), &mut reader);
+
+        assert_eq!(iter.next(), Some(Ok("Test ")));
+        assert_eq!(iter.next(), Some(Ok("Test ")));
+        assert_eq!(iter.iteration(), 2);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(reader.remaining_content(), "Tes");
+
+        let mut reader = ParserInput::new("Test Test Tes");
+        let mut iter = repeat_iter(3, read_text("Test "), &mut reader);
+
+        assert_eq!(iter.next(), Some(Ok("Test ")));
+        assert_eq!(iter.next(), Some(Ok("Test ")));
+        assert_eq!(iter.next(), Some(Err(ParserResultError::NotFound)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(reader.byte_offset(), 0);
+
+        let mut reader = ParserInput::new("aaaa");
+        let count = repeat_iter(..=2, read_text("a"), &mut reader).count();
+        assert_eq!(count, 2);
+        assert_eq!(reader.remaining_content(), "aa");
+    }
 }
 
 #[cfg(test)]
 #[cfg(feature = "alloc")]
 mod test_alloc {
-    use crate::parsers::characters::{ascii_alpha, read_text};
+    use crate::parsers::characters::{ascii_alpha, decimal_digit, read_any_quantified, read_text};
     use crate::parsers::helpers::value;
+    use crate::parsers::sequence::terminated;
 
     use super::*;
 
@@ -366,4 +696,25 @@ mod test_alloc {
         let result = parser(&mut reader);
         assert_eq!(result, Err(ParserResultError::NotFound));
     }
+
+    #[test]
+    fn test_repeat_concat() {
+        let mut reader = ParserInput::new("Test Test Tes");
+        let result = repeat_concat(.., read_text("Test "))(&mut reader);
+        assert_eq!(result, Ok(Cow::Borrowed("Test Test ")));
+
+        let mut reader = ParserInput::new("a1b2c");
+        let mut parser = repeat_concat(1.., terminated(read_any_quantified(1), decimal_digit));
+        let result = parser(&mut reader);
+        assert_eq!(result, Ok(Cow::Owned("ab".to_string())));
+        assert_eq!(reader.remaining_content(), "c");
+
+        let mut reader = ParserInput::new("");
+        let result = repeat_concat(2, |_: &mut ParserInput| Ok("static"))(&mut reader);
+        assert_eq!(result, Ok(Cow::Owned("staticstatic".to_string())));
+
+        let mut reader = ParserInput::new("Tes");
+        let result = repeat_concat(1.., read_text("Test "))(&mut reader);
+        assert_eq!(result, Err(ParserResultError::NotFound));
+    }
 }
@@ -1,41 +1,41 @@
 use crate::parsers::helpers::not_found_restore;
 use crate::result::ParserResult;
-use crate::ParserInput;
+use crate::{InputContent, ParserInput};
 
 /// Helper trait for the [tuple()] combinator.
-pub trait Tuple<'a, C, R, Err> {
+pub trait Tuple<'a, C, R, Err, I: ?Sized = str> {
     /// Parses the input and returns a tuple of results of each parser.
-    fn parse(&mut self, reader: &mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>;
+    fn parse(&mut self, reader: &mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>;
 
     /// Parses the input and returns a tuple of results of each parser.
     /// Between each parser `separator` is executed and its result discarded.
     fn parse_separated<S, RSep>(
         &mut self,
-        reader: &mut ParserInput<'a, Err, C>,
+        reader: &mut ParserInput<'a, Err, C, I>,
         separator: S,
     ) -> ParserResult<R, Err>
     where
-        S: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RSep, Err>;
+        S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>;
 }
 
 /// Applies a tuple of parsers one by one and returns their results as a tuple.
-pub fn tuple<'a, P, C, R, Err>(
+pub fn tuple<'a, P, C, R, Err, I: ?Sized>(
     mut parsers: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: Tuple<'a, C, R, Err>,
+    P: Tuple<'a, C, R, Err, I>,
 {
     move |reader| parsers.parse(reader)
 }
 
 /// Applies a tuple of parsers one by one and returns their results as a tuple.
-pub fn separated_tuple<'a, P, S, C, R, RSep, Err>(
+pub fn separated_tuple<'a, P, S, C, R, RSep, Err, I: ?Sized>(
     mut parsers: P,
     mut separator: S,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<R, Err>
 where
-    P: Tuple<'a, C, R, Err>,
-    S: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RSep, Err>,
+    P: Tuple<'a, C, R, Err, I>,
+    S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>,
 {
     move |reader| parsers.parse_separated(reader, |r| separator(r))
 }
@@ -92,11 +92,11 @@ macro_rules! impl_tuple_body_separated (
 macro_rules! impl_tuple_for_tuples (
     // The actual implementation.
     (__impl $($input:ident: $output:ident)+) => {
-        impl<'a, C, $($input),+, $($output),+,Err> Tuple<'a, C, ($($output),+,), Err> for ($($input),+,)
+        impl<'a, C, $($input),+, $($output),+,Err, I: InputContent + ?Sized> Tuple<'a, C, ($($output),+,), Err, I> for ($($input),+,)
         where
-            $($input: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<$output, Err>),+
+            $($input: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<$output, Err>),+
         {
-            fn parse(&mut self, reader: &mut ParserInput<'a, Err, C>) -> ParserResult<($($output),+,), Err> {
+            fn parse(&mut self, reader: &mut ParserInput<'a, Err, C, I>) -> ParserResult<($($output),+,), Err> {
                 not_found_restore(move |reader| {
                     Ok(impl_tuple_body!(self, reader, $($input)+))
                 })(reader)
@@ -104,12 +104,12 @@ macro_rules! impl_tuple_for_tuples (
 
             fn parse_separated<S, RSep>(
                 &mut self,
-                reader: &mut ParserInput<'a, Err, C>,
+                reader: &mut ParserInput<'a, Err, C, I>,
                 #[allow(unused_variables, unused_mut)]
                 mut separator: S,
             ) -> ParserResult<($($output),+,), Err>
                 where
-                    S: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RSep, Err>,
+                    S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>,
             {
                 not_found_restore(move |reader| {
                     Ok(impl_tuple_body_separated!(self, reader, separator, $($input)+))
@@ -156,10 +156,7 @@ mod test {
     fn test_tuple1() {
         let tuple_size = 1;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -174,10 +171,7 @@ mod test {
     fn test_tuple2() {
         let tuple_size = 2;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -197,10 +191,7 @@ mod test {
     fn test_tuple3() {
         let tuple_size = 3;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -220,10 +211,7 @@ mod test {
     fn test_tuple4() {
         let tuple_size = 4;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -253,10 +241,7 @@ mod test {
     fn test_tuple5() {
         let tuple_size = 5;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -288,10 +273,7 @@ mod test {
     fn test_separated_tuple1() {
         let tuple_size = 1;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -306,10 +288,7 @@ mod test {
     fn test_separated_tuple2() {
         let tuple_size = 2;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -329,10 +308,7 @@ mod test {
     fn test_separated_tuple3() {
         let tuple_size = 3;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -355,10 +331,7 @@ mod test {
     fn test_separated_tuple4() {
         let tuple_size = 4;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -391,10 +364,7 @@ mod test {
     fn test_separated_tuple5() {
         let tuple_size = 5;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
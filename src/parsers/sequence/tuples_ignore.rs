@@ -1,41 +1,41 @@
 use crate::parsers::helpers::not_found_restore;
 use crate::result::ParserResult;
-use crate::ParserInput;
+use crate::{InputContent, ParserInput};
 
 /// Helper trait for the [tuple_ignore()] combinator.
-pub trait TupleIgnore<'a, C, Err> {
+pub trait TupleIgnore<'a, C, Err, I: ?Sized = str> {
     /// Parses the input and returns a tuple of results of each parser.
-    fn parse(&mut self, reader: &mut ParserInput<'a, Err, C>) -> ParserResult<(), Err>;
+    fn parse(&mut self, reader: &mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err>;
 
     /// Parses the input and returns a tuple of results of each parser.
     /// Between each parser `separator` is executed and its result discarded.
     fn parse_separated<S, RSep>(
         &mut self,
-        reader: &mut ParserInput<'a, Err, C>,
+        reader: &mut ParserInput<'a, Err, C, I>,
         separator: S,
     ) -> ParserResult<(), Err>
     where
-        S: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RSep, Err>;
+        S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>;
 }
 
 /// Applies a tuple of parsers one by one ignoring their results.
-pub fn tuple_ignore<'a, P, C, Err>(
+pub fn tuple_ignore<'a, P, C, Err, I: ?Sized>(
     mut parsers: P,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<(), Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err>
 where
-    P: TupleIgnore<'a, C, Err>,
+    P: TupleIgnore<'a, C, Err, I>,
 {
     move |reader| parsers.parse(reader)
 }
 
 /// Applies a tuple of parsers one by one ignoring their results.
-pub fn separated_tuple_ignore<'a, P, S, C, RSep, Err>(
+pub fn separated_tuple_ignore<'a, P, S, C, RSep, Err, I: ?Sized>(
     mut parsers: P,
     mut separator: S,
-) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<(), Err>
+) -> impl FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err>
 where
-    P: TupleIgnore<'a, C, Err>,
-    S: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RSep, Err>,
+    P: TupleIgnore<'a, C, Err, I>,
+    S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>,
 {
     move |reader| parsers.parse_separated(reader, |r| separator(r))
 }
@@ -84,11 +84,11 @@ macro_rules! impl_tuple_body_separated (
 macro_rules! impl_tuple_for_tuples (
     // The actual implementation.
     (__impl $($input:ident: $output:ident)+) => {
-        impl<'a, C, $($input),+, $($output),+,Err> TupleIgnore<'a, C, Err> for ($($input),+,)
+        impl<'a, C, $($input),+, $($output),+,Err, I: InputContent + ?Sized> TupleIgnore<'a, C, Err, I> for ($($input),+,)
         where
-            $($input: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<$output, Err>),+
+            $($input: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<$output, Err>),+
         {
-            fn parse(&mut self, reader: &mut ParserInput<'a, Err, C>) -> ParserResult<(), Err> {
+            fn parse(&mut self, reader: &mut ParserInput<'a, Err, C, I>) -> ParserResult<(), Err> {
                 not_found_restore(move |reader| {
                     impl_tuple_body!(self, reader, $($input)+);
                     Ok(())
@@ -97,12 +97,12 @@ macro_rules! impl_tuple_for_tuples (
 
             fn parse_separated<S, RSep>(
                 &mut self,
-                reader: &mut ParserInput<'a, Err, C>,
+                reader: &mut ParserInput<'a, Err, C, I>,
                 #[allow(unused_variables, unused_mut)]
                 mut separator: S,
             ) -> ParserResult<(), Err>
                 where
-                    S: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<RSep, Err>,
+                    S: FnMut(&mut ParserInput<'a, Err, C, I>) -> ParserResult<RSep, Err>,
             {
                 not_found_restore(move |reader| {
                     impl_tuple_body_separated!(self, reader, separator, $($input)+);
@@ -150,10 +150,7 @@ mod test {
     fn test_tuple1() {
         let tuple_size = 1;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -174,10 +171,7 @@ mod test {
     fn test_tuple2() {
         let tuple_size = 2;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -198,10 +192,7 @@ mod test {
     fn test_tuple3() {
         let tuple_size = 3;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -222,10 +213,7 @@ mod test {
     fn test_tuple4() {
         let tuple_size = 4;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -251,10 +239,7 @@ mod test {
     fn test_tuple5() {
         let tuple_size = 5;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -281,10 +266,7 @@ mod test {
     fn test_separated_tuple1() {
         let tuple_size = 1;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -305,10 +287,7 @@ mod test {
     fn test_separated_tuple2() {
         let tuple_size = 2;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -330,10 +309,7 @@ mod test {
     fn test_separated_tuple3() {
         let tuple_size = 3;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -357,10 +333,7 @@ mod test {
     fn test_separated_tuple4() {
         let tuple_size = 4;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
@@ -389,10 +362,7 @@ mod test {
     fn test_separated_tuple5() {
         let tuple_size = 5;
         for i in 0..tuple_size {
-            let texts: Vec<_> = (0..tuple_size)
-                .into_iter()
-                .map(|i| format!("{}", i))
-                .collect();
+            let texts: Vec<_> = (0..tuple_size).map(|i| format!("{}", i)).collect();
             let input: String = texts.join("|");
             let mut value: Vec<_> = texts.iter().map(|t| read_text(t.as_str())).collect();
 
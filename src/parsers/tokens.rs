@@ -0,0 +1,42 @@
+use crate::input::Token;
+use crate::result::{Needed, ParserResult, ParserResultError};
+use crate::ParserInput;
+
+/// Reads any token.
+pub fn read_token<'a, C, Err, K, I: ?Sized>(
+    reader: &mut ParserInput<'a, Err, C, [Token<'a, K, I>]>,
+) -> ParserResult<&'a Token<'a, K, I>, Err> {
+    match reader.read_token() {
+        Some(v) => Ok(v),
+        None if reader.is_partial() => Err(ParserResultError::Incomplete(Needed::Size(1))),
+        None => Err(ParserResultError::NotFound),
+    }
+}
+
+/// Reads a token of a specific `kind`.
+#[allow(clippy::type_complexity)]
+pub fn read_kind<'a, C, Err, K: PartialEq, I: ?Sized>(
+    kind: K,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, [Token<'a, K, I>]>) -> ParserResult<&'a Token<'a, K, I>, Err>
+{
+    read_token_if(move |token| token.kind() == &kind)
+}
+
+/// Reads a token that satisfies `verifier`.
+#[allow(clippy::type_complexity)]
+pub fn read_token_if<'a, C, Err, K, I: ?Sized, V>(
+    mut verifier: V,
+) -> impl FnMut(&mut ParserInput<'a, Err, C, [Token<'a, K, I>]>) -> ParserResult<&'a Token<'a, K, I>, Err>
+where
+    V: FnMut(&Token<'a, K, I>) -> bool,
+{
+    move |reader| match reader.peek_token() {
+        Some(token) if verifier(token) => {
+            reader.read_token();
+            Ok(token)
+        }
+        Some(_) => Err(ParserResultError::NotFound),
+        None if reader.is_partial() => Err(ParserResultError::Incomplete(Needed::Size(1))),
+        None => Err(ParserResultError::NotFound),
+    }
+}
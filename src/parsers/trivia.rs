@@ -0,0 +1,229 @@
+use crate::parsers::helpers::not_found_restore;
+use crate::result::{ParserResult, ParserResultError};
+use crate::ParserInput;
+
+/// Skips the trivia following the rules of the reader. It always succeeds unless the
+/// reader is partial and the trivia can continue after the end of the content.
+/// See [ParserInput::skip_trivia()].
+pub fn skip_trivia<'a, C, Err>(reader: &mut ParserInput<'a, Err, C>) -> ParserResult<&'a str, Err> {
+    reader.skip_trivia().map_err(ParserResultError::Incomplete)
+}
+
+/// Applies a parser and skips the trivia after it.
+///
+/// Using it for every token only requires to skip the trivia once at the start of the input.
+pub fn lexeme<'a, P, C, R, Err>(
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+{
+    move |reader| {
+        let result = parser(reader)?;
+        skip_trivia(reader)?;
+
+        Ok(result)
+    }
+}
+
+/// Applies a parser skipping the trivia before and after it.
+///
+/// # Example
+///
+/// ```
+/// # use jpar::{ParserInput, Trivia};
+/// # use jpar::characters::{decimal_digit1, read_char};
+/// # use jpar::sequence::tuple;
+/// # use jpar::trivia::token;
+/// let mut reader = ParserInput::new("1 /* plus */ + // two\n 2");
+/// reader.set_trivia(
+///     Trivia::new()
+///         .with_line_comments(&["//"])
+///         .with_block_comments(&[("/*", "*/")]),
+/// );
+///
+/// let mut parser = tuple((
+///     token(decimal_digit1),
+///     token(read_char('+')),
+///     token(decimal_digit1),
+/// ));
+///
+/// assert_eq!(parser(&mut reader), Ok(("1", '+', "2")));
+/// assert!(reader.is_end());
+/// ```
+pub fn token<'a, P, C, R, Err>(
+    mut parser: P,
+) -> impl FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>
+where
+    P: FnMut(&mut ParserInput<'a, Err, C>) -> ParserResult<R, Err>,
+{
+    not_found_restore(move |reader| {
+        skip_trivia(reader)?;
+        let result = parser(reader)?;
+        skip_trivia(reader)?;
+
+        Ok(result)
+    })
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use crate::input::Trivia;
+    use crate::parsers::characters::{decimal_digit1, read_char, read_text};
+    use crate::parsers::sequence::tuple;
+    use crate::result::Needed;
+
+    use super::*;
+
+    fn reader(content: &str) -> ParserInput<'_> {
+        let mut reader = ParserInput::new(content);
+        reader.set_trivia(
+            Trivia::new()
+                .with_line_comments(&["//", "#"])
+                .with_block_comments(&[("/*", "*/"), ("(*", "*)")]),
+        );
+        reader
+    }
+
+    #[test]
+    fn test_skip_trivia() {
+        let mut reader = reader(" \t// a\n# b\r\n/* c\n d */(* e *)x");
+        assert_eq!(
+            skip_trivia(&mut reader),
+            Ok(" \t// a\n# b\r\n/* c\n d */(* e *)")
+        );
+        assert_eq!(reader.read(), Some('x'));
+        assert_eq!(skip_trivia(&mut reader), Ok(""));
+
+        // Default trivia.
+        let mut reader = ParserInput::new(" \n// a");
+        assert_eq!(reader.skip_trivia(), Ok(" \n"));
+    }
+
+    #[test]
+    fn test_block_comments() {
+        let mut reader = reader("/* a /* b */ c */");
+        assert_eq!(reader.skip_trivia(), Ok("/* a /* b */ "));
+
+        let mut reader = self::reader("/* a /* b */ c */ x");
+        reader.set_trivia(reader.trivia().with_nested_comments(true));
+        assert_eq!(reader.skip_trivia(), Ok("/* a /* b */ c */ "));
+
+        // Unclosed comments are not trivia.
+        let mut reader = self::reader(" /* a /* b */");
+        reader.set_trivia(reader.trivia().with_nested_comments(true));
+        assert_eq!(reader.skip_trivia(), Ok(" "));
+    }
+
+    #[test]
+    fn test_token_lexeme() {
+        let mut reader = reader(" // a\n let /* b */ x");
+
+        assert_eq!(
+            token(read_text("var"))(&mut reader),
+            Err(ParserResultError::NotFound)
+        );
+        assert_eq!(reader.byte_offset(), 0);
+
+        assert_eq!(token(read_text("let"))(&mut reader), Ok("let"));
+        assert_eq!(lexeme(read_char('x'))(&mut reader), Ok('x'));
+        assert!(reader.is_end());
+    }
+
+    #[test]
+    fn test_partial() {
+        let mut parser = tuple((token(decimal_digit1), token(decimal_digit1)));
+
+        for content in &["1 /* pl", "1 // plus", "1 /", "1 (", "1 #"] {
+            let mut reader = reader(content);
+            reader.set_partial(true);
+            assert!(
+                parser(&mut reader).unwrap_err().is_incomplete(),
+                "Content: {}",
+                content
+            );
+        }
+
+        let mut reader = reader(" /");
+        reader.set_partial(true);
+        assert_eq!(
+            skip_trivia(&mut reader),
+            Err(ParserResultError::Incomplete(Needed::Size(1)))
+        );
+        assert_eq!(reader.byte_offset(), 0);
+
+        let mut reader = self::reader("1 /* plus */ 2 ");
+        reader.set_partial(true);
+        assert_eq!(lexeme(decimal_digit1)(&mut reader), Ok("1"));
+        assert_eq!(lexeme(decimal_digit1)(&mut reader), Ok("2"));
+        assert!(reader.is_end());
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]
+mod test_alloc {
+    use crate::input::Trivia;
+    use crate::parsers::branch::alternative;
+    use crate::parsers::characters::read_text;
+    use crate::parsers::sequence::tuple;
+
+    use super::*;
+
+    #[test]
+    fn test_collect_comments() {
+        let mut reader = ParserInput::new("// a\nlet /* b */ x # c");
+        reader.set_trivia(
+            Trivia::new()
+                .with_line_comments(&["//", "#"])
+                .with_block_comments(&[("/*", "*/")]),
+        );
+        reader.set_comment_collecting(true);
+
+        let result = alternative((
+            tuple((token(read_text("let")), token(read_text("y")))),
+            tuple((token(read_text("let")), token(read_text("x")))),
+        ))(&mut reader);
+        assert_eq!(result, Ok(("let", "x")));
+
+        let comments: Vec<_> = reader.comments().iter().map(|v| v.content()).collect();
+        assert_eq!(comments, vec!["// a", "/* b */", "# c"]);
+        assert_eq!(reader.comments()[1].start_cursor().line(), 2);
+
+        reader.set_comment_collecting(false);
+        assert!(reader.comments().is_empty());
+    }
+
+    #[test]
+    fn test_collect_comments_backtracking() {
+        let mut reader = ParserInput::new("a /* b */ x");
+        reader.set_trivia(Trivia::new().with_block_comments(&[("/*", "*/")]));
+        reader.set_comment_collecting(true);
+
+        // The comment skipped by the failed branch is read as content by the other one.
+        let result = alternative((
+            tuple((token(read_text("a")), token(read_text("y")))),
+            tuple((read_text("a "), read_text("/* b */ x"))),
+        ))(&mut reader);
+        assert_eq!(result, Ok(("a ", "/* b */ x")));
+        assert!(reader.comments().is_empty());
+
+        // The comment first skipped after backtracking from a further position is kept.
+        let mut reader = ParserInput::new("a /* b */ x");
+        reader.set_trivia(Trivia::new().with_block_comments(&[("/*", "*/")]));
+        reader.set_comment_collecting(true);
+
+        let result = alternative((
+            tuple((read_text("a /* b */"), read_text("y"))),
+            tuple((token(read_text("a")), token(read_text("x")))),
+        ))(&mut reader);
+        assert_eq!(result, Ok(("a", "x")));
+
+        let comments: Vec<_> = reader.comments().iter().map(|v| v.content()).collect();
+        assert_eq!(comments, vec!["/* b */"]);
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
 pub fn char_verifier(value: char) -> impl Fn(usize, char) -> bool {
     move |_, c| value == c
@@ -4,13 +4,19 @@ use crate::Cursor;
 pub type ParserResult<T, Err = ()> = Result<T, ParserResultError<Err>>;
 
 /// The type of errors that parser method can return.
+///
+/// The errors are located by a [Cursor] by default. Use [ParserResultError::map_location()]
+/// to locate them with other types, e.g. with an owned [SpanInfo](crate::SpanInfo).
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ParserResultError<Err> {
+pub enum ParserResultError<Err, L = Cursor> {
     NotFound,
-    Error((Cursor, Err)),
+    Error((L, Err)),
+    /// The parser reached the end of a partial input and needs more data to decide.
+    /// See [ParserInput::set_partial()](crate::ParserInput::set_partial).
+    Incomplete(Needed),
 }
 
-impl<Err> ParserResultError<Err> {
+impl<Err, L> ParserResultError<Err, L> {
     // GETTERS ----------------------------------------------------------------
 
     pub fn is_not_found(&self) -> bool {
@@ -21,12 +27,36 @@ impl<Err> ParserResultError<Err> {
         matches!(self, ParserResultError::Error(_))
     }
 
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self, ParserResultError::Incomplete(_))
+    }
+
     // METHODS ----------------------------------------------------------------
 
-    pub fn unwrap_error(self) -> (Cursor, Err) {
+    pub fn unwrap_error(self) -> (L, Err) {
         match self {
             ParserResultError::Error(e) => e,
-            ParserResultError::NotFound => unreachable!(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Maps the location of the error keeping the rest of variants.
+    pub fn map_location<L2>(self, map_fn: impl FnOnce(L) -> L2) -> ParserResultError<Err, L2> {
+        match self {
+            ParserResultError::NotFound => ParserResultError::NotFound,
+            ParserResultError::Error((location, error)) => {
+                ParserResultError::Error((map_fn(location), error))
+            }
+            ParserResultError::Incomplete(needed) => ParserResultError::Incomplete(needed),
         }
     }
 }
+
+/// The amount of data a parser needs to continue parsing a partial input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Needed {
+    /// The parser cannot know how much data it needs.
+    Unknown,
+    /// The parser needs at least this number of bytes.
+    Size(usize),
+}
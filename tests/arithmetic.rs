@@ -1,6 +1,6 @@
 use jpar::branch::alternative;
-use jpar::characters::{decimal_digit1, read_any_of, read_char, ucd_inline_whitespace0};
-use jpar::helpers::map_result;
+use jpar::characters::{read_any_of, read_char, ucd_inline_whitespace0};
+use jpar::numbers::unsigned;
 use jpar::sequence::{delimited, repeat_and_fold, tuple};
 use jpar::verifiers::text_verifier;
 use jpar::ParserInput;
@@ -16,14 +16,7 @@ use jpar::ParserResult;
 fn factor(input: &mut ParserInput) -> ParserResult<i64> {
     delimited(
         ucd_inline_whitespace0,
-        alternative((
-            map_result(decimal_digit1, |_, digits| {
-                unsafe { std::str::from_utf8_unchecked(digits.as_bytes()) }
-                    .parse()
-                    .unwrap()
-            }),
-            delimited(read_char('('), expr, read_char(')')),
-        )),
+        alternative((unsigned(), delimited(read_char('('), expr, read_char(')')))),
         ucd_inline_whitespace0,
     )(input)
 }
@@ -64,6 +57,7 @@ fn expr(input: &mut ParserInput) -> ParserResult<i64> {
 }
 
 #[test]
+#[allow(clippy::eq_op, clippy::erasing_op, clippy::identity_op)]
 fn test() {
     let data = "  2*2 / ( 5 - 1) + 3 / 4 * (2 - 7 + 567 *12 /2) + 3*(1+2*( 45 /2));";
     let mut reader = ParserInput::new(data);
@@ -0,0 +1,43 @@
+//! Checks that jpar can be used from a `no_std` crate.
+//! Run it without the standard library: `cargo test --no-default-features --test no_std`.
+#![no_std]
+
+// The test harness requires std but the parsers below do not use it.
+extern crate std;
+
+use jpar::characters::{read_any_of1, read_char, ucd_inline_whitespace0};
+use jpar::combinator::end;
+use jpar::numbers::unsigned;
+use jpar::sequence::{delimited, repeat_and_count, terminated, tuple};
+use jpar::ParserInput;
+use jpar::ParserResult;
+
+// Parser definition
+
+// A setting of a firmware config file: `<name> = <value>\n`.
+fn setting<'a>(input: &mut ParserInput<'a>) -> ParserResult<(&'a str, u32)> {
+    let (name, _, value) = terminated(
+        tuple((
+            read_any_of1(|_, c| c.is_ascii_alphanumeric() || c == '_'),
+            delimited(
+                ucd_inline_whitespace0,
+                read_char('='),
+                ucd_inline_whitespace0,
+            ),
+            unsigned(),
+        )),
+        read_char('\n'),
+    )(input)?;
+
+    Ok((name, value))
+}
+
+#[test]
+fn test() {
+    let data = "baud_rate = 115200\nretries=3\nled_pin = 13\n";
+    let mut reader = ParserInput::new(data);
+
+    assert_eq!(setting(&mut reader), Ok(("baud_rate", 115200)));
+    assert_eq!(repeat_and_count(.., setting)(&mut reader), Ok(2));
+    assert_eq!(end(&mut reader), Ok(()));
+}